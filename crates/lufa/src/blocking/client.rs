@@ -0,0 +1,137 @@
+use std::{future::Future, sync::Arc};
+
+use snafu::IntoError;
+use tokio::runtime::Runtime;
+
+use crate::{
+    error::{IoSnafu, Result},
+    Language, LufaBuilder, Session, SessionInfo,
+};
+
+use super::{AuthService, BillingService, OrdersService, ProfileService, RawService};
+
+/// A blocking [`Lufa`](crate::Lufa) client.
+///
+/// Clones share the same session and runtime.
+#[derive(Debug, Clone)]
+pub struct Lufa {
+    inner: crate::Lufa,
+    runtime: Arc<Runtime>,
+}
+
+impl Lufa {
+    /// Creates a client against the production Lufa API
+    /// with the default configuration.
+    ///
+    /// Use [`Lufa::builder`] to customize the client.
+    pub fn new(language: Language) -> Result<Self> {
+        Self::builder().language(language).build_blocking()
+    }
+
+    /// Creates a [`LufaBuilder`] for configuring a client,
+    /// to be built with [`LufaBuilder::build_blocking`].
+    pub fn builder() -> LufaBuilder {
+        LufaBuilder::new()
+    }
+
+    /// Creates a client against the production Lufa API
+    /// from a previously exported session.
+    pub fn from_session(session: Session) -> Result<Self> {
+        Self::builder().session(session).build_blocking()
+    }
+
+    /// Wraps an async client, sharing its session.
+    pub fn from_async(inner: crate::Lufa) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| IoSnafu.into_error(e))?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client this client wraps.
+    pub fn as_async(&self) -> &crate::Lufa {
+        &self.inner
+    }
+
+    /// Returns a client that issues its calls in the given
+    /// language, sharing this client's session and runtime.
+    pub fn with_language(&self, language: Language) -> Self {
+        Self {
+            inner: self.inner.with_language(language),
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// Whether the client holds a session that has not
+    /// expired yet.
+    pub fn is_logged_in(&self) -> bool {
+        self.block_on(self.inner.is_logged_in())
+    }
+
+    /// Returns information about the logged in user, or
+    /// `None` if the client is not logged in.
+    pub fn session(&self) -> Option<SessionInfo> {
+        self.block_on(self.inner.session())
+    }
+
+    /// Exports the current session so that it can be
+    /// persisted and restored later.
+    pub fn export_session(&self) -> Result<Session> {
+        self.block_on(self.inner.export_session())
+    }
+
+    /// Restores a previously exported session into this
+    /// client, replacing its current login state.
+    pub fn restore_session(&self, session: &Session) -> Result<()> {
+        self.block_on(self.inner.restore_session(session))
+    }
+
+    pub fn auth(&self) -> AuthService<'_> {
+        AuthService(self)
+    }
+
+    pub fn billing(&self) -> BillingService<'_> {
+        BillingService(self)
+    }
+
+    pub fn profile(&self) -> ProfileService<'_> {
+        ProfileService(self)
+    }
+
+    pub fn orders(&self) -> OrdersService<'_> {
+        OrdersService(self)
+    }
+
+    pub fn raw(&self) -> RawService<'_> {
+        RawService(self)
+    }
+
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl LufaBuilder {
+    /// Builds a blocking client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{blocking, Language, Result};
+    ///
+    /// fn build_client() -> Result<blocking::Lufa> {
+    ///   blocking::Lufa::builder()
+    ///     .language(Language::French)
+    ///     .build_blocking()
+    /// }
+    /// ```
+    pub fn build_blocking(self) -> Result<Lufa> {
+        Lufa::from_async(self.build()?)
+    }
+}
@@ -0,0 +1,30 @@
+//! A synchronous facade over the async [`Lufa`](crate::Lufa)
+//! client, for programs that do not otherwise use Tokio.
+//!
+//! The blocking client runs the async client on a runtime of
+//! its own. It must not be used from within an async context,
+//! as blocking on a runtime from inside another one panics.
+//!
+//! # Examples
+//!
+//! ```
+//! use lufa::{blocking, Language, Result};
+//!
+//! fn print_profile() -> Result<()> {
+//!   let client = blocking::Lufa::new(Language::English)?;
+//!   client.auth().login("bob@example.com", "passw0rd")?;
+//!
+//!   let profile = client.profile().get()?;
+//!   println!("Account under {}", profile.donation_name);
+//!   Ok(())
+//! }
+//! ```
+
+mod client;
+pub use client::*;
+
+mod services;
+pub use services::*;
+
+mod session;
+pub use session::*;
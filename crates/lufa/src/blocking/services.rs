@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{models, Result};
+
+use super::Lufa;
+
+/// The blocking counterpart of [`crate::AuthService`].
+#[derive(Debug, Clone)]
+pub struct AuthService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> AuthService<'a> {
+    /// Logs a user into the Lufa API using their email
+    /// and password credentials.
+    pub fn login<E: AsRef<str>, P: AsRef<str>>(&self, email: E, password: P) -> Result<()> {
+        let client = self.0.as_async();
+        self.0.block_on(client.auth().login(email, password))
+    }
+
+    /// Logs a user out of the Lufa API.
+    pub fn logout(&self) -> Result<()> {
+        let client = self.0.as_async();
+        self.0.block_on(client.auth().logout())
+    }
+}
+
+/// The blocking counterpart of [`crate::BillingService`].
+#[derive(Debug, Clone)]
+pub struct BillingService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> BillingService<'a> {
+    /// Retrieves the configured payment cards for
+    /// the currently logged in user.
+    pub fn get_cards(&self) -> Result<HashMap<usize, models::billing::Card>> {
+        let client = self.0.as_async();
+        self.0.block_on(client.billing().get_cards())
+    }
+
+    /// Retrieves the configured payment cards as raw JSON.
+    pub fn get_cards_raw(&self) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.billing().get_cards_raw())
+    }
+
+    /// Retrieves the list of completed transactions for
+    /// the currently logged in user.
+    pub fn get_transactions(&self) -> Result<Vec<models::billing::Transaction>> {
+        let client = self.0.as_async();
+        self.0.block_on(client.billing().get_transactions())
+    }
+
+    /// Retrieves the list of completed transactions as
+    /// raw JSON.
+    pub fn get_transactions_raw(&self) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.billing().get_transactions_raw())
+    }
+}
+
+/// The blocking counterpart of [`crate::ProfileService`].
+#[derive(Debug, Clone)]
+pub struct ProfileService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> ProfileService<'a> {
+    /// Retrieves the profile of the currently
+    /// logged in user.
+    pub fn get(&self) -> Result<models::profile::Profile> {
+        let client = self.0.as_async();
+        self.0.block_on(client.profile().get())
+    }
+
+    /// Retrieves the profile of the currently logged in
+    /// user as raw JSON.
+    pub fn get_raw(&self) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.profile().get_raw())
+    }
+}
+
+/// The blocking counterpart of [`crate::OrdersService`].
+#[derive(Debug, Clone)]
+pub struct OrdersService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> OrdersService<'a> {
+    pub fn get_active(&self) -> Result<Option<models::orders::Order>> {
+        let client = self.0.as_async();
+        self.0.block_on(client.orders().get_active())
+    }
+
+    pub fn get_active_raw(&self) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.orders().get_active_raw())
+    }
+
+    pub fn track(&self, order_id: &models::OrderId) -> Result<models::orders::OrderTracking> {
+        let client = self.0.as_async();
+        self.0.block_on(client.orders().track(order_id))
+    }
+
+    pub fn track_raw(&self, order_id: &models::OrderId) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.orders().track_raw(order_id))
+    }
+}
+
+/// The blocking counterpart of [`crate::RawService`].
+#[derive(Debug, Clone)]
+pub struct RawService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> RawService<'a> {
+    /// Sends a `GET` request to an endpoint.
+    pub fn get(&self, path: &str) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.raw().get(path))
+    }
+
+    /// Posts a urlencoded form to an endpoint.
+    pub fn post_form<Form: Serialize>(&self, path: &str, form: &Form) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.raw().post_form(path, form))
+    }
+
+    /// Posts a JSON payload to an endpoint.
+    pub fn post_json<Payload: Serialize>(
+        &self,
+        path: &str,
+        payload: &Payload,
+    ) -> Result<serde_json::Value> {
+        let client = self.0.as_async();
+        self.0.block_on(client.raw().post_json(path, payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lufa_mock::{MockServer, EMAIL, ORDER_ID, PASSWORD};
+
+    use crate::blocking::Lufa;
+
+    #[test]
+    fn test_blocking_services() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start()).unwrap();
+
+        let client = Lufa::builder()
+            .base_url(server.url())
+            .build_blocking()
+            .unwrap();
+        client.auth().login(EMAIL, PASSWORD).unwrap();
+
+        assert!(client.profile().get().is_ok());
+        assert!(client.billing().get_cards().is_ok());
+        assert!(client.billing().get_transactions().is_ok());
+
+        let order = client.orders().get_active().unwrap().unwrap();
+        assert_eq!(order.id, ORDER_ID);
+        assert!(client.orders().track(&order.id).is_ok());
+        assert_eq!(
+            client.orders().track_raw(&order.id).unwrap()["order_id"],
+            ORDER_ID
+        );
+        assert_eq!(
+            client
+                .raw()
+                .get("/superMarket/GetUserOrderDetails")
+                .unwrap()["orderId"],
+            ORDER_ID
+        );
+
+        client.auth().logout().unwrap();
+        assert!(client.profile().get().is_err());
+    }
+}
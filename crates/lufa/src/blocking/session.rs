@@ -0,0 +1,89 @@
+use std::{future::Future, path::PathBuf};
+
+use snafu::IntoError;
+
+use crate::{
+    error::{IoSnafu, Result},
+    Session,
+};
+
+/// The blocking counterpart of [`crate::FileSessionStore`],
+/// storing sessions in the same format.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    inner: crate::FileSessionStore,
+}
+
+impl FileSessionStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            inner: crate::FileSessionStore::new(path),
+        }
+    }
+
+    /// Loads the stored session, returning `None` if
+    /// no session has been saved yet.
+    pub fn load(&self) -> Result<Option<Session>> {
+        block_on(self.inner.load())?
+    }
+
+    /// Saves the session, overwriting any previously
+    /// stored session.
+    pub fn save(&self, session: &Session) -> Result<()> {
+        block_on(self.inner.save(session))?
+    }
+
+    /// Removes the stored session, if any.
+    pub fn clear(&self) -> Result<()> {
+        block_on(self.inner.clear())?
+    }
+}
+
+// Runs a file operation to completion on a
+// short-lived runtime of its own
+fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| IoSnafu.into_error(e))?;
+
+    Ok(runtime.block_on(future))
+}
+
+#[cfg(test)]
+mod tests {
+    use lufa_mock::{MockServer, EMAIL, PASSWORD, USER_ID};
+
+    use super::FileSessionStore;
+    use crate::blocking::Lufa;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(MockServer::start()).unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("lufa-blocking-session-{}.json", std::process::id()));
+        let store = FileSessionStore::new(&path);
+
+        let client = Lufa::builder()
+            .base_url(server.url())
+            .build_blocking()
+            .unwrap();
+        client.auth().login(EMAIL, PASSWORD).unwrap();
+        store.save(&client.export_session().unwrap()).unwrap();
+
+        let session = store.load().unwrap().unwrap();
+        assert_eq!(session.user_id(), USER_ID);
+
+        let client = Lufa::builder()
+            .base_url(server.url())
+            .session(session)
+            .build_blocking()
+            .unwrap();
+        assert!(client.profile().get().is_ok());
+
+        store.clear().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+    }
+}
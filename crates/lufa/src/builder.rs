@@ -0,0 +1,402 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Url,
+};
+use snafu::IntoError;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{
+    error::{InvalidHeaderSnafu, ReqwestSnafu, Result, UrlParseSnafu},
+    CredentialProvider, Language, Limiter, Lufa, RateLimit, RecordingTransport, ReplayTransport,
+    ReqwestTransport, RetryPolicy, Session, State, Transport, DEFAULT_API_BASE_URL, NAME, VERSION,
+};
+
+/// A builder for configuring a [`Lufa`] client.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use lufa::{Lufa, Language, Result};
+///
+/// fn build_client() -> Result<Lufa> {
+///   Lufa::builder()
+///     .base_url("http://localhost:8080")
+///     .language(Language::French)
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .header("X-Request-Source", "cron")
+///     .build()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LufaBuilder {
+    base_url: String,
+    language: Language,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: String,
+    headers: Vec<(String, String)>,
+    session: Option<Session>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    transport: Option<Arc<dyn Transport>>,
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    retry: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    max_in_flight: Option<usize>,
+
+    // A limiter shared with other clients, which takes
+    // precedence over `rate_limit` and `max_in_flight`
+    limiter: Option<Arc<Limiter>>,
+
+    #[cfg(feature = "tracing")]
+    trace_key: Option<[u8; 32]>,
+}
+
+impl Default for LufaBuilder {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_API_BASE_URL.to_string(),
+            language: Language::English,
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: format!("{}/{}", NAME, VERSION),
+            headers: Vec::new(),
+            session: None,
+            credentials: None,
+            transport: None,
+            record: None,
+            replay: None,
+            retry: RetryPolicy::default(),
+            rate_limit: None,
+            max_in_flight: None,
+            limiter: None,
+            #[cfg(feature = "tracing")]
+            trace_key: None,
+        }
+    }
+}
+
+impl LufaBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the base URL that all requests are issued against.
+    ///
+    /// Defaults to `https://montreal.lufa.com`.
+    pub fn base_url<U: AsRef<str>>(mut self, base_url: U) -> Self {
+        self.base_url = base_url.as_ref().to_string();
+        self
+    }
+
+    /// Sets the language in which the API responds.
+    ///
+    /// Defaults to [`Language::English`].
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets the total timeout for each request, from
+    /// connecting until the response body has been read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout for the connect phase of each request.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes all requests through the given proxy URL,
+    /// e.g. `http://proxy.local:3128`.
+    pub fn proxy<U: AsRef<str>>(mut self, proxy: U) -> Self {
+        self.proxy = Some(proxy.as_ref().to_string());
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to `lufa/<version>`.
+    pub fn user_agent<U: AsRef<str>>(mut self, user_agent: U) -> Self {
+        self.user_agent = user_agent.as_ref().to_string();
+        self
+    }
+
+    /// Adds a header that is sent with every request.
+    ///
+    /// Adding a header with the same name again sends both
+    /// values rather than replacing the first one.
+    pub fn header<K: AsRef<str>, V: AsRef<str>>(mut self, name: K, value: V) -> Self {
+        self.headers
+            .push((name.as_ref().to_string(), value.as_ref().to_string()));
+        self
+    }
+
+    /// Restores a previously exported [`Session`] into
+    /// the client, so that it starts out logged in.
+    pub fn session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Sets the provider the client uses to log in on its own,
+    /// both lazily before the first call and again whenever
+    /// the session expires.
+    pub fn credentials<P: CredentialProvider + 'static>(mut self, provider: P) -> Self {
+        self.credentials = Some(Arc::new(provider));
+        self
+    }
+
+    /// Sets the [`Transport`] used to send requests, e.g. a
+    /// [`MemoryTransport`](crate::MemoryTransport) in tests.
+    ///
+    /// Defaults to a [`ReqwestTransport`] configured from the
+    /// builder's settings. A custom transport is used as-is:
+    /// the timeouts, proxy, user agent and headers set on the
+    /// builder do not apply to it.
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sets how requests that failed for transient reasons
+    /// are retried.
+    ///
+    /// Defaults to [`RetryPolicy::default`].
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Limits the rate at which the client, and all of its
+    /// clones, send requests.
+    ///
+    /// Defaults to no limit.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Limits the number of requests that the client, and
+    /// all of its clones, have in flight at the same time.
+    ///
+    /// Defaults to no limit.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    /// Sets the secret key that user IDs are hashed with
+    /// before being recorded in traces.
+    ///
+    /// Defaults to a random key, shared by every client of
+    /// the process. Set a key of your own, and keep it
+    /// secret, to correlate users across runs.
+    #[cfg(feature = "tracing")]
+    pub fn trace_key(mut self, key: [u8; 32]) -> Self {
+        self.trace_key = Some(key);
+        self
+    }
+
+    // Makes every client built from this builder share
+    // a single limiter, built from its settings
+    pub(crate) fn _share_limiter(mut self) -> Self {
+        let limiter = Limiter::new(self.rate_limit, self.max_in_flight);
+        self.limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    // The first setting that every client built from this
+    // builder would share, and that must therefore be set
+    // per client: a transport keeps its own cookies, a
+    // cassette is written by a single recorder, and a
+    // session belongs to a single account
+    pub(crate) fn _shared_setting(&self) -> Option<&'static str> {
+        if self.transport.is_some() {
+            Some("transport")
+        } else if self.record.is_some() {
+            Some("record")
+        } else if self.session.is_some() {
+            Some("session")
+        } else {
+            None
+        }
+    }
+
+    /// Records every request and response into a cassette
+    /// file at the given path, with cookies and credentials
+    /// redacted. The file is written once the client and
+    /// all of its clones are dropped. See [`RecordingTransport`].
+    pub fn record<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.record = Some(path.into());
+        self
+    }
+
+    /// Serves responses from a previously recorded cassette
+    /// file instead of the network. See [`ReplayTransport`].
+    ///
+    /// Takes precedence over [`LufaBuilder::transport`].
+    pub fn replay<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// Fails if the base URL, proxy URL or any of the
+    /// headers are invalid, or if the cassette to replay
+    /// cannot be read.
+    pub fn build(self) -> Result<Lufa> {
+        let base_url = Url::parse(&self.base_url).map_err(|e| UrlParseSnafu.into_error(e))?;
+
+        let headers = self._headers()?;
+
+        let mut transport: Arc<dyn Transport> = match (self.replay, self.transport) {
+            (Some(path), _) => Arc::new(ReplayTransport::open(path)?),
+            (None, Some(transport)) => transport,
+            (None, None) => Arc::new(Self::_reqwest_transport(
+                self.user_agent,
+                headers,
+                self.timeout,
+                self.connect_timeout,
+                self.proxy,
+            )?),
+        };
+
+        if let Some(path) = self.record {
+            transport = Arc::new(RecordingTransport::new(transport, path));
+        }
+
+        let lufa = Lufa {
+            transport,
+            base_url,
+            language: self.language,
+            state: Arc::new(RwLock::new(self.session.as_ref().map(State::from))),
+            credentials: self.credentials,
+            login_generation: Arc::new(Mutex::new(0)),
+            retry: self.retry,
+            limiter: self
+                .limiter
+                .unwrap_or_else(|| Arc::new(Limiter::new(self.rate_limit, self.max_in_flight))),
+            #[cfg(feature = "tracing")]
+            trace_key: self
+                .trace_key
+                .unwrap_or_else(crate::trace::default_trace_key),
+        };
+
+        if let Some(session) = self.session.as_ref() {
+            lufa._restore_cookies(session)?;
+        }
+
+        Ok(lufa)
+    }
+
+    // The headers sent with every request, in the order
+    // they were added
+    fn _headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers.iter() {
+            let header_name = HeaderName::from_bytes(name.as_bytes());
+            let header_value = HeaderValue::from_str(value);
+
+            match (header_name, header_value) {
+                (Ok(n), Ok(v)) => {
+                    headers.append(n, v);
+                }
+                _ => return InvalidHeaderSnafu { name: name.clone() }.fail(),
+            }
+        }
+
+        Ok(headers)
+    }
+
+    fn _reqwest_transport(
+        user_agent: String,
+        headers: HeaderMap,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+        proxy: Option<String>,
+    ) -> Result<ReqwestTransport> {
+        let mut client = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .default_headers(headers);
+
+        if let Some(timeout) = timeout {
+            client = client.timeout(timeout);
+        }
+
+        if let Some(timeout) = connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+
+        if let Some(proxy) = proxy {
+            let proxy = reqwest::Proxy::all(proxy).map_err(|e| ReqwestSnafu.into_error(e))?;
+            client = client.proxy(proxy);
+        }
+
+        ReqwestTransport::from_builder(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Language, Lufa};
+
+    #[test]
+    fn test_build_default() {
+        let client = Lufa::builder().build().unwrap();
+
+        let url = client._build_url("/users/profileData").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://montreal.lufa.com/en/users/profileData"
+        );
+    }
+
+    #[test]
+    fn test_build_base_url() {
+        let client = Lufa::builder()
+            .base_url("http://127.0.0.1:8080/")
+            .language(Language::French)
+            .build()
+            .unwrap();
+
+        let url = client._build_url("login").unwrap();
+        assert_eq!(url.as_str(), "http://127.0.0.1:8080/fr/login");
+    }
+
+    #[test]
+    fn test_headers() {
+        let headers = Lufa::builder()
+            .header("X-Request-Source", "cron")
+            .header("x-request-source", "backfill")
+            .header("X-Trace", "1")
+            ._headers()
+            .unwrap();
+
+        let sources: Vec<_> = headers.get_all("x-request-source").iter().collect();
+        assert_eq!(sources, ["cron", "backfill"]);
+        assert_eq!(headers.len(), 3);
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let res = Lufa::builder().base_url("not a url").build();
+        assert!(matches!(res, Err(Error::UrlParse { .. })));
+
+        let res = Lufa::builder().header("bad header", "value").build();
+        assert!(matches!(res, Err(Error::InvalidHeader { .. })));
+
+        let res = Lufa::builder().proxy("::not a proxy::").build();
+        assert!(res.is_err());
+    }
+}
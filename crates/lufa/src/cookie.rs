@@ -0,0 +1,323 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    string::FromUtf8Error,
+    time::{Duration, SystemTime},
+};
+
+use snafu::{ensure, IntoError, ResultExt, Snafu};
+
+use crate::models;
+
+/// An error raised by a cookie that could not be understood.
+#[derive(Snafu, Debug)]
+#[snafu(visibility(pub(crate)))]
+#[non_exhaustive]
+pub enum CookieError {
+    /// The cookie is not a `name=value` pair.
+    #[snafu(display("malformed cookie: {:?}", cookie))]
+    Malformed { cookie: String },
+
+    /// The `lufaState` cookie does not start with the
+    /// 40-character hexadecimal signature.
+    #[snafu(display("lufaState is not signed: {:?}", value))]
+    Unsigned { value: String },
+
+    /// The `lufaState` cookie is not valid UTF-8 once
+    /// urldecoded.
+    #[snafu(display("lufaState is not urlencoded UTF-8: {}", source))]
+    StateEncoding { source: FromUtf8Error },
+
+    /// The `lufaState` cookie is not the PHP-serialized
+    /// array the client expects.
+    #[snafu(display("lufaState could not be deserialized: {}", source))]
+    StateFormat { source: serde_php::Error },
+}
+
+/// A cookie sent by the server in a `Set-Cookie` header.
+///
+/// Only the attributes the client cares about are kept.
+/// Unknown attributes, as well as an `Expires` date that
+/// cannot be parsed, are ignored like browsers do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+
+    // The lifetime of the cookie, in seconds. Zero or
+    // less means that the cookie must be removed.
+    pub max_age: Option<i64>,
+
+    // When the cookie expires, if given as a date
+    pub expires: Option<SystemTime>,
+}
+
+impl SetCookie {
+    /// Parses the value of a `Set-Cookie` header.
+    pub fn parse(header: &str) -> Result<Self, CookieError> {
+        let mut parts = header.split(';').map(str::trim);
+
+        let (name, value) = parts
+            .next()
+            .and_then(|pair| pair.split_once('='))
+            .filter(|(name, _)| !name.trim().is_empty())
+            .ok_or_else(|| {
+                MalformedSnafu {
+                    cookie: header.to_string(),
+                }
+                .build()
+            })?;
+
+        let mut cookie = SetCookie {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+            max_age: None,
+            expires: None,
+        };
+
+        for (key, value) in parts.filter_map(|attribute| attribute.split_once('=')) {
+            match key.trim().to_ascii_lowercase().as_str() {
+                "max-age" => cookie.max_age = value.trim().parse().ok(),
+                "expires" => cookie.expires = parse_http_date(value.trim()),
+                _ => {}
+            }
+        }
+
+        Ok(cookie)
+    }
+
+    /// When the cookie expires, as of `now`. `Max-Age`
+    /// takes precedence over `Expires`, and a `Max-Age`
+    /// too large to represent means no known expiry.
+    pub fn expires_at(&self, now: SystemTime) -> Option<SystemTime> {
+        match self.max_age {
+            Some(max_age) => now.checked_add(Duration::from_secs(max_age.max(0) as u64)),
+            None => self.expires,
+        }
+    }
+
+    /// Whether the server is removing the cookie rather
+    /// than setting it: its value is empty or `deleted`,
+    /// or it has already expired.
+    pub fn is_removal(&self) -> bool {
+        let now = SystemTime::now();
+
+        self.value.is_empty()
+            || self.value == "deleted"
+            || self.expires_at(now).is_some_and(|at| at <= now)
+    }
+}
+
+/// The signed `lufaState` cookie, which identifies the
+/// logged in user.
+///
+/// Its value is a 40-character hexadecimal signature
+/// followed by a urlencoded, PHP-serialized array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LufaStateCookie {
+    pub signature: String,
+    pub state: models::cookies::LufaState,
+}
+
+impl LufaStateCookie {
+    const SIGNATURE_LEN: usize = 40;
+
+    /// Encodes the cookie back into its value.
+    pub fn encode(&self) -> String {
+        fn php_str(s: &str) -> String {
+            format!("s:{}:\"{}\";", s.len(), s)
+        }
+
+        let state = &self.state;
+        let serialized = format!(
+            "a:4:{{i:0;{}i:1;{}i:2;i:{};i:3;a:2:{{{}{}{}{}}}}}",
+            php_str(&state.0),
+            php_str(&state.1),
+            state.2,
+            php_str("user_email"),
+            php_str(&state.3.user_email),
+            php_str("first_name"),
+            php_str(&state.3.first_name),
+        );
+
+        format!("{}{}", self.signature, urlencoding::encode(&serialized))
+    }
+}
+
+impl FromStr for LufaStateCookie {
+    type Err = CookieError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let signature = value.get(..Self::SIGNATURE_LEN).unwrap_or_default();
+        ensure!(
+            signature.len() == Self::SIGNATURE_LEN
+                && signature.bytes().all(|b| b.is_ascii_hexdigit()),
+            UnsignedSnafu { value }
+        );
+
+        let encoded = &value[Self::SIGNATURE_LEN..];
+        let decoded = urlencoding::decode(encoded).context(StateEncodingSnafu)?;
+        let state = serde_php::from_bytes(decoded.as_bytes()).context(StateFormatSnafu)?;
+
+        Ok(LufaStateCookie {
+            signature: signature.to_string(),
+            state,
+        })
+    }
+}
+
+// Decodes the value of a `lufaState` cookie
+pub(crate) fn decode_lufa_state(value: &str) -> crate::Result<models::cookies::LufaState> {
+    value
+        .parse::<LufaStateCookie>()
+        .map(|cookie| cookie.state)
+        .map_err(|e| crate::CookieSnafu.into_error(e))
+}
+
+// Encodes a `lufaState` cookie value the way the server does,
+// except that the signature is zeroes since we cannot sign it
+pub(crate) fn encode_lufa_state(state: &models::cookies::LufaState) -> String {
+    LufaStateCookie {
+        signature: "0".repeat(LufaStateCookie::SIGNATURE_LEN),
+        state: state.clone(),
+    }
+    .encode()
+}
+
+// Parses a `Cookie` header into its pairs, by name. Pairs
+// without a `=` are skipped, and values may contain `=`.
+pub(crate) fn parse_cookie_header(header: &str) -> HashMap<String, String> {
+    header
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+// Parses a cookie date, either as in RFC 1123 or with dashes
+// between the day, month and year as PHP formats it, e.g.
+// `Thu, 01-Jan-1970 00:00:01 GMT`
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let value = value.replace('-', " ");
+    let date = chrono::DateTime::parse_from_rfc2822(&value).ok()?;
+
+    Some(SystemTime::from(date))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{parse_cookie_header, CookieError, LufaStateCookie, SetCookie};
+    use crate::models;
+
+    fn state() -> models::cookies::LufaState {
+        models::cookies::LufaState(
+            "123456".to_string(),
+            "bob@example.com".to_string(),
+            0,
+            models::cookies::LufaStateInfo {
+                user_email: "bob@example.com".to_string(),
+                first_name: "Bob".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_set_cookie() {
+        let cookie = SetCookie::parse("lufaState=a=b; Max-Age=2592000; path=/; HttpOnly").unwrap();
+        assert_eq!(cookie.name, "lufaState");
+        assert_eq!(cookie.value, "a=b");
+        assert_eq!(cookie.max_age, Some(2592000));
+        assert!(!cookie.is_removal());
+
+        let now = SystemTime::now();
+        assert_eq!(
+            cookie.expires_at(now),
+            Some(now + Duration::from_secs(2592000))
+        );
+
+        let forever = SetCookie::parse("lufaState=x; Max-Age=9223372036854775807").unwrap();
+        assert_eq!(forever.expires_at(now), None);
+        assert!(!forever.is_removal());
+
+        assert!(matches!(
+            SetCookie::parse("HttpOnly; path=/"),
+            Err(CookieError::Malformed { .. })
+        ));
+        assert!(SetCookie::parse("=value").is_err());
+    }
+
+    #[test]
+    fn test_set_cookie_removal() {
+        let deleted = SetCookie::parse(
+            "lufaState=deleted; expires=Thu, 01-Jan-1970 00:00:01 GMT; Max-Age=0; path=/",
+        )
+        .unwrap();
+        assert!(deleted.is_removal());
+        assert_eq!(
+            deleted.expires,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1))
+        );
+
+        let expired =
+            SetCookie::parse("lufaState=abc; expires=Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert!(expired.is_removal());
+
+        // An unparseable date is ignored
+        let cookie = SetCookie::parse("lufaState=abc; expires=tomorrow").unwrap();
+        assert_eq!(cookie.expires, None);
+        assert!(!cookie.is_removal());
+    }
+
+    #[test]
+    fn test_lufa_state_roundtrip() {
+        let cookie = LufaStateCookie {
+            signature: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            state: state(),
+        };
+
+        let parsed: LufaStateCookie = cookie.encode().parse().unwrap();
+        assert_eq!(parsed, cookie);
+    }
+
+    #[test]
+    fn test_lufa_state_malformed() {
+        let encoded = LufaStateCookie {
+            signature: "0".repeat(40),
+            state: state(),
+        }
+        .encode();
+
+        assert!(matches!(
+            "short".parse::<LufaStateCookie>(),
+            Err(CookieError::Unsigned { .. })
+        ));
+        assert!(matches!(
+            format!("{}{}", "z".repeat(40), &encoded[40..]).parse::<LufaStateCookie>(),
+            Err(CookieError::Unsigned { .. })
+        ));
+        assert!(matches!(
+            // A multi-byte character straddling the signature
+            format!("{}é{}", "0".repeat(39), &encoded[40..]).parse::<LufaStateCookie>(),
+            Err(CookieError::Unsigned { .. })
+        ));
+        assert!(matches!(
+            format!("{}%FF", "0".repeat(40)).parse::<LufaStateCookie>(),
+            Err(CookieError::StateEncoding { .. })
+        ));
+        assert!(matches!(
+            format!("{}a:1:{{", "0".repeat(40)).parse::<LufaStateCookie>(),
+            Err(CookieError::StateFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cookie_header() {
+        let cookies = parse_cookie_header("PHPSESSID=abc; flag; token=a=b=; =orphan");
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies["PHPSESSID"], "abc");
+        assert_eq!(cookies["token"], "a=b=");
+    }
+}
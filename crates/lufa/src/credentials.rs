@@ -0,0 +1,186 @@
+use std::fmt;
+
+use async_trait::async_trait;
+
+use crate::Result;
+
+/// An email and password pair used to log into Lufa.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub email: String,
+    pub password: String,
+}
+
+impl Credentials {
+    pub fn new<E: AsRef<str>, P: AsRef<str>>(email: E, password: P) -> Self {
+        Self {
+            email: email.as_ref().to_string(),
+            password: password.as_ref().to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("email", &self.email)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A source of [`Credentials`] that the client uses to log in
+/// again on its own when the session has expired.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use lufa::{CredentialProvider, Credentials, Lufa, Result};
+///
+/// #[derive(Debug)]
+/// struct FromEnv;
+///
+/// #[async_trait]
+/// impl CredentialProvider for FromEnv {
+///   async fn credentials(&self) -> Result<Credentials> {
+///     Ok(Credentials::new(
+///       std::env::var("LUFA_EMAIL").unwrap_or_default(),
+///       std::env::var("LUFA_PASSWORD").unwrap_or_default(),
+///     ))
+///   }
+/// }
+///
+/// fn build_client() -> Result<Lufa> {
+///   Lufa::builder().credentials(FromEnv).build()
+/// }
+/// ```
+#[async_trait]
+pub trait CredentialProvider: fmt::Debug + Send + Sync {
+    async fn credentials(&self) -> Result<Credentials>;
+}
+
+#[async_trait]
+impl CredentialProvider for Credentials {
+    async fn credentials(&self) -> Result<Credentials> {
+        Ok(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lufa_mock::{MockServer, EMAIL, PASSWORD};
+    use reqwest::{Method, StatusCode};
+
+    use super::Credentials;
+    use crate::{Lufa, LufaError, MemoryResponse, MemoryTransport};
+
+    #[test]
+    fn test_debug_redacts_password() {
+        let credentials = Credentials::new("bob@example.com", "passw0rd");
+        let debug = format!("{:?}", credentials);
+
+        assert!(debug.contains("bob@example.com"));
+        assert!(!debug.contains("passw0rd"));
+    }
+
+    #[tokio::test]
+    async fn test_reauthenticate_on_expiry() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::logged_in("123456", "bob@example.com"),
+        );
+        transport.respond(
+            Method::GET,
+            "/users/profileData",
+            MemoryResponse::ok().redirect_to("/login"),
+        );
+        transport.respond(
+            Method::GET,
+            "/users/profileData",
+            MemoryResponse::ok().body("{}"),
+        );
+
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .credentials(Credentials::new("bob@example.com", "passw0rd"))
+            .build()
+            .unwrap();
+
+        let response = client._get("/users/profileData").await.unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.text(), "{}");
+
+        let paths: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|r| r.url.path().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "/en/users/profileData",
+                "/en/login",
+                "/en/users/profileData"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_page_is_not_expiry() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::GET,
+            "/login",
+            MemoryResponse::ok().body("<html></html>"),
+        );
+
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .credentials(Credentials::new("bob@example.com", "passw0rd"))
+            .build()
+            .unwrap();
+
+        // Requesting the login page is not a redirect to it,
+        // and must not make the client log in again
+        let response = client._get("/login").await.unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_expired_without_credentials() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::GET,
+            "/users/profileData",
+            MemoryResponse::new(StatusCode::UNAUTHORIZED),
+        );
+
+        let client = Lufa::builder().transport(transport).build().unwrap();
+        let res = client._get("/users/profileData").await;
+
+        assert!(matches!(
+            res.unwrap_err().lufa_error(),
+            Some(LufaError::SessionExpired { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_relogin_after_server_expiry() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder()
+            .base_url(server.url())
+            .credentials(Credentials::new(EMAIL, PASSWORD))
+            .build()
+            .unwrap();
+
+        assert!(client.profile().get().await.is_ok());
+
+        server.expire_sessions();
+        assert!(client.profile().get().await.is_ok());
+        assert_eq!(server.active_sessions(), 1);
+    }
+}
@@ -1,6 +1,4 @@
-use core::fmt;
-use std::string::FromUtf8Error;
-
+use reqwest::StatusCode;
 use snafu::{Backtrace, Snafu};
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -8,23 +6,25 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 #[derive(Snafu, Debug)]
 #[snafu(visibility(pub))]
 pub enum Error {
+    #[snafu(display("{}", source))]
     Lufa {
         source: LufaError,
         backtrace: Backtrace,
     },
 
-    UrlEncoding {
-        source: FromUtf8Error,
+    UrlParse {
+        source: url::ParseError,
         backtrace: Backtrace,
     },
 
-    UrlParse {
-        source: url::ParseError,
+    #[snafu(display("{}", source))]
+    Cookie {
+        source: crate::CookieError,
         backtrace: Backtrace,
     },
 
-    SerdePhp {
-        source: serde_php::Error,
+    Io {
+        source: std::io::Error,
         backtrace: Backtrace,
     },
 
@@ -33,6 +33,30 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    FormEncoding {
+        source: serde_urlencoded::ser::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("no recorded response for {} {}", method, path))]
+    CassetteMiss {
+        method: String,
+        path: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "a pool's builder cannot set a {}, as all accounts would share it",
+        setting
+    ))]
+    SharedPoolSetting {
+        setting: &'static str,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("invalid header: {}", name))]
+    InvalidHeader { name: String, backtrace: Backtrace },
+
     #[snafu(display("Reqwest Error: {}\nFound at {}", source, backtrace))]
     Reqwest {
         source: reqwest::Error,
@@ -40,17 +64,115 @@ pub enum Error {
     },
 }
 
-#[derive(Debug, Clone)]
+/// An error reported by, or about, the Lufa API itself.
+///
+/// Every variant carries the endpoint path that was being
+/// called, e.g. `/users/profileData`.
+#[derive(Snafu, Debug, Clone)]
 #[non_exhaustive]
-pub struct LufaError {
-    pub message: String,
+pub enum LufaError {
+    /// The call requires a logged in user, but the
+    /// client has not logged in.
+    #[snafu(display("not logged in (calling {})", endpoint))]
+    NotLoggedIn { endpoint: String },
+
+    /// The server refused the email and password, i.e. it
+    /// answered the login without starting a session.
+    ///
+    /// A login that fails with a non-successful HTTP status
+    /// is reported as [`LufaError::HttpStatus`] instead.
+    #[snafu(display("invalid credentials (calling {})", endpoint))]
+    InvalidCredentials { endpoint: String },
+
+    /// The server-side session has expired and the client
+    /// could not log in again on its own.
+    #[snafu(display("session expired (calling {})", endpoint))]
+    SessionExpired { endpoint: String },
+
+    /// The server responded with a non-successful HTTP status.
+    #[snafu(display("{} responded with {}: {}", endpoint, status, body_excerpt))]
+    HttpStatus {
+        endpoint: String,
+        status: StatusCode,
+        body_excerpt: String,
+    },
+
+    /// The server responded with a body that could not
+    /// be understood. The raw body is kept for diagnostics.
+    #[snafu(display("unexpected response from {}: {}", endpoint, reason))]
+    UnexpectedResponse {
+        endpoint: String,
+        raw: String,
+        reason: String,
+    },
+
+    /// The server reported that the call failed, i.e.
+    /// `success` was `false` in its response.
+    #[snafu(display("{} failed: {}", endpoint, message))]
+    ApiFailure { endpoint: String, message: String },
+}
+
+impl LufaError {
+    /// The endpoint path that was being called.
+    pub fn endpoint(&self) -> &str {
+        match self {
+            Self::NotLoggedIn { endpoint }
+            | Self::InvalidCredentials { endpoint }
+            | Self::SessionExpired { endpoint }
+            | Self::HttpStatus { endpoint, .. }
+            | Self::UnexpectedResponse { endpoint, .. }
+            | Self::ApiFailure { endpoint, .. } => endpoint,
+        }
+    }
+
+    /// The body of a response that could not be parsed,
+    /// so that it can still be inspected.
+    pub fn raw(&self) -> Option<&str> {
+        match self {
+            Self::UnexpectedResponse { raw, .. } => Some(raw),
+            _ => None,
+        }
+    }
 }
 
-impl fmt::Display for LufaError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)?;
-        Ok(())
+impl Error {
+    /// The underlying [`LufaError`], if this error
+    /// was reported by or about the Lufa API.
+    pub fn lufa_error(&self) -> Option<&LufaError> {
+        match self {
+            Self::Lufa { source, .. } => Some(source),
+            _ => None,
+        }
     }
 }
 
-impl std::error::Error for LufaError {}
+#[cfg(test)]
+mod tests {
+    use reqwest::StatusCode;
+    use snafu::IntoError;
+
+    use super::{Error, LufaError, LufaSnafu};
+
+    #[test]
+    fn test_lufa_error() {
+        let err: Error = LufaSnafu.into_error(LufaError::HttpStatus {
+            endpoint: "/users/profileData".to_string(),
+            status: StatusCode::BAD_GATEWAY,
+            body_excerpt: "Bad Gateway".to_string(),
+        });
+
+        let lufa_error = err.lufa_error().unwrap();
+        assert_eq!(lufa_error.endpoint(), "/users/profileData");
+        assert!(matches!(
+            lufa_error,
+            LufaError::HttpStatus {
+                status: StatusCode::BAD_GATEWAY,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "/users/profileData responded with 502 Bad Gateway: Bad Gateway"
+        );
+    }
+}
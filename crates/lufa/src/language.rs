@@ -1,11 +1,38 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-#[derive(Debug, Clone, Copy)]
+use snafu::Snafu;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
     English,
     French,
 }
 
+/// The error returned when a string does not name
+/// a language supported by Lufa.
+#[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[snafu(display("invalid language: {:?}", input))]
+pub struct ParseLanguageError {
+    input: String,
+}
+
+impl ParseLanguageError {
+    /// The string that could not be parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Language {
+    /// The other language, e.g. French for English.
+    pub fn other(&self) -> Self {
+        match self {
+            Self::English => Self::French,
+            Self::French => Self::English,
+        }
+    }
+}
+
 impl fmt::Display for Language {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -15,16 +42,44 @@ impl fmt::Display for Language {
     }
 }
 
-impl From<&str> for Language {
-    fn from(value: &str) -> Self {
-        match value {
-            "en" => Language::English,
-            "fr" => Language::French,
-            _ => panic!("invalid language"),
+/// Parses a language from its code, a locale tag such as
+/// `fr-CA` or `en_US.UTF-8`, or its name, ignoring case.
+///
+/// # Examples
+///
+/// ```
+/// use lufa::Language;
+///
+/// assert_eq!("fr-CA".parse(), Ok(Language::French));
+/// assert_eq!("English".parse(), Ok(Language::English));
+/// assert!("de".parse::<Language>().is_err());
+/// ```
+impl FromStr for Language {
+    type Err = ParseLanguageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lowercase = s.trim().to_lowercase();
+
+        // Only the primary subtag of a locale tag matters,
+        // e.g. `fr` in `fr-CA` or `en` in `en_US.UTF-8`
+        let primary = lowercase.split(['-', '_', '.']).next().unwrap_or_default();
+
+        match primary {
+            "en" | "eng" | "english" | "anglais" => Ok(Language::English),
+            "fr" | "fra" | "fre" | "french" | "français" | "francais" => Ok(Language::French),
+            _ => ParseLanguageSnafu { input: s }.fail(),
         }
     }
 }
 
+impl TryFrom<&str> for Language {
+    type Error = ParseLanguageError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
 impl From<Language> for &str {
     fn from(value: Language) -> Self {
         match value {
@@ -33,3 +88,41 @@ impl From<Language> for &str {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Language;
+
+    #[test]
+    fn test_parse() {
+        for s in ["en", "EN", "en-CA", "en_US.UTF-8", "English", " eng "] {
+            assert_eq!(s.parse(), Ok(Language::English), "{}", s);
+        }
+
+        for s in ["fr", "fr-CA", "fr_CA", "French", "Français", "fra"] {
+            assert_eq!(s.parse(), Ok(Language::French), "{}", s);
+        }
+
+        for s in ["", "de", "de-CH", "enx", "-fr"] {
+            let err = s.parse::<Language>().unwrap_err();
+            assert_eq!(err.input(), s);
+        }
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Language::try_from("fr-CA"), Ok(Language::French));
+        assert_eq!(
+            Language::try_from("xx").unwrap_err().to_string(),
+            "invalid language: \"xx\""
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for language in [Language::English, Language::French] {
+            assert_eq!(language.to_string().parse(), Ok(language));
+            assert_eq!(language.other().other(), language);
+        }
+    }
+}
@@ -1,20 +1,38 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod cookie;
+mod credentials;
 mod error;
 mod language;
+mod limit;
+mod pool;
+mod response;
+mod retry;
 mod services;
+mod session;
+mod trace;
+mod transport;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::SystemTime};
 
-use reqwest::{
-    cookie::{CookieStore, Jar},
-    Response, Url,
-};
+use reqwest::{Method, StatusCode, Url};
 use serde::Serialize;
 use snafu::IntoError;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+use trace::trace_event;
 
+pub use builder::*;
+pub use cookie::*;
+pub use credentials::*;
 pub use error::*;
 pub use language::*;
+pub use limit::*;
+pub use pool::*;
+pub use retry::*;
 pub use services::*;
+pub use session::*;
+pub use transport::*;
 
 pub mod models {
     pub use lufa_models::*;
@@ -24,80 +42,143 @@ const NAME: &str = env!("CARGO_PKG_NAME");
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const DEFAULT_API_BASE_URL: &str = "https://montreal.lufa.com";
 
-#[allow(dead_code)]
 #[derive(Debug, Clone)]
 struct State {
-    user_id: String,
+    user_id: models::UserId,
     email: String,
+    lufa_state: models::cookies::LufaState,
+
+    // When the client logged in and when the `lufaState`
+    // cookie expires, if known
+    logged_in_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Lufa {
-    client: Arc<reqwest::Client>,
-    jar: Arc<Jar>,
+    transport: Arc<dyn Transport>,
     state: Arc<RwLock<Option<State>>>,
+    credentials: Option<Arc<dyn CredentialProvider>>,
+    login_generation: Arc<Mutex<u64>>,
+    retry: RetryPolicy,
+    limiter: Arc<Limiter>,
+    base_url: Url,
     language: Language,
+
+    // The key that user IDs are hashed with in traces
+    #[cfg(feature = "tracing")]
+    trace_key: [u8; 32],
 }
 
 impl Lufa {
+    /// Creates a client against the production Lufa API
+    /// with the default configuration.
+    ///
+    /// Use [`Lufa::builder`] to customize the client.
     pub fn new(language: Language) -> Result<Self> {
-        let jar = Arc::new(Jar::default());
-        let client = reqwest::Client::builder()
-            .cookie_provider(jar.clone())
-            .build()
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?;
-
-        Ok(Self {
-            client: Arc::new(client),
-            jar,
+        Self::builder().language(language).build()
+    }
+
+    /// Creates a [`LufaBuilder`] for configuring a client.
+    pub fn builder() -> LufaBuilder {
+        LufaBuilder::new()
+    }
+
+    /// The language in which the API responds.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns a client that issues its calls in the given
+    /// language, sharing this client's session, transport
+    /// and limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result};
+    ///
+    /// async fn bilingual_receipt(client: &Lufa) -> Result<()> {
+    ///   let english = client.with_language(Language::English).orders().get_active().await?;
+    ///   let french = client.with_language(Language::French).orders().get_active().await?;
+    ///
+    ///   for (en, fr) in english.iter().zip(french.iter()) {
+    ///     for (en, fr) in en.items.iter().zip(fr.items.iter()) {
+    ///       println!("{} / {}", en.name, fr.name);
+    ///     }
+    ///   }
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn with_language(&self, language: Language) -> Self {
+        Self {
             language,
-            state: Arc::new(RwLock::new(None)),
-        })
+            ..self.clone()
+        }
+    }
+
+    /// Statistics about the time spent waiting on the
+    /// client's rate limit and in-flight request limit,
+    /// across all of its clones.
+    pub fn limiter_metrics(&self) -> LimiterMetrics {
+        self.limiter.metrics()
     }
 
     fn _cookies(&self) -> HashMap<String, String> {
-        let cookie_str = match self.jar.cookies(&self._build_url("").unwrap()) {
-            Some(hv) => hv.to_str().unwrap().to_string(),
-            None => String::default(),
-        };
-
-        let cookies: HashMap<String, String> = cookie_str
-            .split(";")
-            .map(|t| {
-                let mut kv = t.trim().split("=").map(|kv| kv.to_string());
-                (kv.next().unwrap(), kv.next().unwrap())
-            })
-            .collect();
+        let cookie_str = self
+            ._build_url("")
+            .ok()
+            .and_then(|url| self.transport.cookies(&url))
+            .unwrap_or_default();
 
-        cookies
+        parse_cookie_header(&cookie_str)
     }
 
-    pub(crate) async fn guard_logged_in(&self) -> Result<()> {
-        match self.is_logged_in().await {
-            true => Ok(()),
-            false => Err(LufaSnafu.into_error(LufaError {
-                message: format!("not logged in"),
+    pub(crate) async fn guard_logged_in(&self, endpoint: &str) -> Result<()> {
+        if self.is_logged_in().await {
+            return Ok(());
+        }
+
+        // Log in lazily if the client knows how to
+        match self.credentials.as_ref() {
+            Some(provider) => {
+                let generation = *self.login_generation.lock().await;
+                self._reauthenticate(provider.as_ref(), generation).await
+            }
+            None => Err(LufaSnafu.into_error(LufaError::NotLoggedIn {
+                endpoint: endpoint.to_string(),
             })),
         }
     }
 
-    pub(crate) async fn is_logged_in(&self) -> bool {
-        let has_state = self.state.read().await.is_some();
+    /// Whether the client holds a session that has not
+    /// expired yet.
+    ///
+    /// The server may still end the session early, in
+    /// which case this only turns `false` once a request
+    /// finds out.
+    pub async fn is_logged_in(&self) -> bool {
+        let has_session = self
+            .state
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|state| state.expires_at.is_none_or(|at| at > SystemTime::now()));
 
         let cookies = self._cookies();
         let has_cookie = cookies.contains_key("lufaState");
 
-        return has_state && has_cookie;
+        has_session && has_cookie
     }
 
-    pub(crate) async fn user_id(&self) -> Result<String> {
+    pub(crate) async fn user_id(&self, endpoint: &str) -> Result<models::UserId> {
         let state = self.state.read().await;
-        let user_id = state
-            .clone()
-            .map(|s| s.user_id)
-            .ok_or(LufaSnafu.into_error(LufaError {
-                message: format!("not logged in"),
-            }))?;
+        let user_id = state.clone().map(|s| s.user_id).ok_or_else(|| {
+            LufaSnafu.into_error(LufaError::NotLoggedIn {
+                endpoint: endpoint.to_string(),
+            })
+        })?;
 
         Ok(user_id)
     }
@@ -105,92 +186,199 @@ impl Lufa {
     fn _build_url(&self, path: &str) -> Result<reqwest::Url> {
         let path: &str = path.trim_start_matches('/');
         let language: &str = self.language.into();
-        let full: String = format!("{}/{}/{}", DEFAULT_API_BASE_URL, language, path);
+        let base: &str = self.base_url.as_str().trim_end_matches('/');
+        let full: String = format!("{}/{}/{}", base, language, path);
 
-        Url::parse(&full).map_err(|e| UrlParseSnafu.into_error(e.into()))
+        Url::parse(&full).map_err(|e| UrlParseSnafu.into_error(e))
     }
 
-    pub(crate) async fn _get(&self, path: &str) -> Result<Response> {
-        let res = self
-            .client
-            .get(self._build_url(path)?)
-            .header("User-Agent", format!("{}/{}", NAME, VERSION))
-            .send()
-            .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?;
+    pub(crate) fn _request(&self, method: Method, path: &str) -> Result<HttpRequest> {
+        Ok(HttpRequest::new(method, self._build_url(path)?))
+    }
+
+    // Sends a request as-is, without checking whether
+    // the session has expired
+    pub(crate) async fn _send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let _permit = self.limiter.acquire().await;
+        self.transport.send(request).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "lufa.request",
+            skip_all,
+            fields(
+                endpoint = endpoint,
+                method = %request.method,
+                language = %self.language,
+                user = tracing::field::Empty,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                retries = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn _execute(&self, endpoint: &str, request: HttpRequest) -> Result<HttpResponse> {
+        #[cfg(feature = "tracing")]
+        let start = std::time::Instant::now();
+
+        #[cfg(feature = "tracing")]
+        if let Some(state) = self.state.read().await.as_ref() {
+            tracing::Span::current().record(
+                "user",
+                trace::user_hash(&self.trace_key, state.user_id.as_str()),
+            );
+        }
+
+        let result = self._execute_with_relogin(endpoint, request).await;
+
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("latency_ms", start.elapsed().as_millis() as u64);
+            if let Ok(response) = result.as_ref() {
+                span.record("status", response.status.as_u16());
+            }
+        }
 
-        Ok(res)
+        result
     }
 
-    pub(crate) async fn _post<Body: Into<reqwest::Body>>(
+    // Sends a request, logging in again and retrying the
+    // request once if the session has expired in the meantime
+    async fn _execute_with_relogin(
+        &self,
+        endpoint: &str,
+        request: HttpRequest,
+    ) -> Result<HttpResponse> {
+        let retry = request.clone();
+        let generation = *self.login_generation.lock().await;
+
+        let response = self._send_with_retry(request).await?;
+        if !Self::_is_session_expired(&retry, &response) {
+            return Ok(response);
+        }
+
+        trace_event!(warn, "session expired");
+
+        match self.credentials.as_ref() {
+            Some(provider) => {
+                self._reauthenticate(provider.as_ref(), generation).await?;
+                self._send_with_retry(retry).await
+            }
+            None => {
+                let mut state = self.state.write().await;
+                *state = None;
+
+                Err(LufaSnafu.into_error(LufaError::SessionExpired {
+                    endpoint: endpoint.to_string(),
+                }))
+            }
+        }
+    }
+
+    // The server answers requests made with an expired session
+    // by redirecting to the login page. A request for the login
+    // page itself, or for any other path ending in `/login`,
+    // is not such a redirect.
+    fn _is_session_expired(request: &HttpRequest, response: &HttpResponse) -> bool {
+        let request_path = request.url.path().trim_end_matches('/');
+        let response_path = response.url.path().trim_end_matches('/');
+
+        let redirected_to_login =
+            request_path != response_path && response_path.ends_with("/login");
+
+        response.status == StatusCode::UNAUTHORIZED || redirected_to_login
+    }
+
+    // Logs in again using the configured credentials, unless
+    // another request already did so since `generation` was read
+    async fn _reauthenticate(
+        &self,
+        provider: &dyn CredentialProvider,
+        generation: u64,
+    ) -> Result<()> {
+        let mut current = self.login_generation.lock().await;
+        if *current != generation {
+            return Ok(());
+        }
+
+        {
+            let mut state = self.state.write().await;
+            *state = None;
+        }
+
+        trace_event!(info, "logging in again");
+
+        let credentials = provider.credentials().await?;
+        self._login(&credentials.email, &credentials.password)
+            .await?;
+
+        *current += 1;
+
+        Ok(())
+    }
+
+    pub(crate) async fn _get(&self, path: &str) -> Result<HttpResponse> {
+        self._execute(path, self._request(Method::GET, path)?).await
+    }
+
+    pub(crate) async fn _post<Body: Into<Vec<u8>>>(
         &self,
         path: &str,
         body: Body,
-    ) -> Result<Response> {
-        let res = self
-            .client
-            .post(self._build_url(path)?)
-            .header("User-Agent", format!("{}/{}", NAME, VERSION))
-            .body(body)
-            .send()
+    ) -> Result<HttpResponse> {
+        self._execute(path, self._request(Method::POST, path)?.body(body))
             .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?;
-
-        Ok(res)
     }
 
     pub(crate) async fn _post_json<Payload: Serialize>(
         &self,
         path: &str,
         payload: &Payload,
-    ) -> Result<Response> {
-        let res = self
-            .client
-            .post(self._build_url(path)?)
-            .header("User-Agent", format!("{}/{}", NAME, VERSION))
-            .json(payload)
-            .send()
+    ) -> Result<HttpResponse> {
+        self._execute(path, self._request(Method::POST, path)?.json(payload)?)
             .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?;
-
-        Ok(res)
     }
 
     pub(crate) async fn _post_form<Form: Serialize>(
         &self,
         path: &str,
         form: &Form,
-    ) -> Result<Response> {
-        let req = self
-            .client
-            .post(self._build_url(path)?)
-            .header("User-Agent", format!("{}/{}", NAME, VERSION))
-            .form(form)
-            .build()
-            .unwrap();
-
-        let res = self
-            .client
-            .execute(req)
+    ) -> Result<HttpResponse> {
+        self._execute(path, self._request(Method::POST, path)?.form(form)?)
             .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?;
+    }
 
-        Ok(res)
+    // Posts a form to an endpoint that only reads data,
+    // so that the request may be retried
+    pub(crate) async fn _post_form_idempotent<Form: Serialize>(
+        &self,
+        path: &str,
+        form: &Form,
+    ) -> Result<HttpResponse> {
+        let request = self._request(Method::POST, path)?.form(form)?;
+        self._execute(path, request.idempotent(true)).await
     }
 
-    pub fn auth(&self) -> AuthService {
+    pub fn auth(&self) -> AuthService<'_> {
         AuthService(self)
     }
 
-    pub fn billing(&self) -> BillingService {
+    pub fn billing(&self) -> BillingService<'_> {
         BillingService(self)
     }
 
-    pub fn profile(&self) -> ProfileService {
+    pub fn profile(&self) -> ProfileService<'_> {
         ProfileService(self)
     }
 
-    pub fn orders(&self) -> OrdersService {
+    pub fn orders(&self) -> OrdersService<'_> {
         OrdersService(self)
     }
+
+    pub fn raw(&self) -> RawService<'_> {
+        RawService(self)
+    }
 }
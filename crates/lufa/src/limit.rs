@@ -0,0 +1,298 @@
+use std::{sync::Mutex, time::Duration};
+
+use tokio::{
+    sync::{Semaphore, SemaphorePermit},
+    time::Instant,
+};
+
+/// A token-bucket rate limit on the requests a client sends.
+///
+/// The bucket holds up to `burst` tokens and refills at a
+/// rate of `requests` tokens per `per`. Every request takes
+/// a token, waiting for one if the bucket is empty.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use lufa::{Lufa, RateLimit, Result};
+///
+/// fn build_client() -> Result<Lufa> {
+///   Lufa::builder()
+///     .rate_limit(RateLimit::new(10, Duration::from_secs(1)).burst(2))
+///     .max_in_flight(4)
+///     .build()
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allows `requests` requests every `per`, all of
+    /// which may be sent in a single burst.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let requests = requests.max(1);
+        Self {
+            requests,
+            per,
+            burst: requests,
+        }
+    }
+
+    /// Allows `requests` requests per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Sets the number of requests that may be sent back
+    /// to back before the rate applies. Values below 1
+    /// are treated as 1.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    // The time it takes to refill a single token
+    fn interval(&self) -> Duration {
+        self.per / self.requests
+    }
+}
+
+/// Statistics about the time a client spent waiting
+/// on its rate limit and in-flight request limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LimiterMetrics {
+    // The number of requests that went through the limiter
+    pub requests: u64,
+
+    // The number of requests that had to wait
+    pub throttled: u64,
+
+    // The total and longest time spent waiting
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+// The limits of a client, shared by all of its clones
+#[derive(Debug, Default)]
+pub(crate) struct Limiter {
+    rate: Option<(RateLimit, Mutex<Bucket>)>,
+    in_flight: Option<Semaphore>,
+    metrics: Mutex<LimiterMetrics>,
+}
+
+impl Limiter {
+    pub(crate) fn new(rate: Option<RateLimit>, max_in_flight: Option<usize>) -> Self {
+        Self {
+            rate: rate.map(|rate| {
+                let bucket = Bucket {
+                    tokens: rate.burst as f64,
+                    refilled_at: Instant::now(),
+                };
+
+                (rate, Mutex::new(bucket))
+            }),
+            in_flight: max_in_flight.map(|max| Semaphore::new(max.max(1))),
+            metrics: Mutex::default(),
+        }
+    }
+
+    // Waits until a request may be sent. The request
+    // counts as in flight until the permit is dropped.
+    //
+    // The token is waited for first, so that requests
+    // held back by the rate limit do not take up in-flight
+    // slots while they are not sending anything.
+    pub(crate) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let start = Instant::now();
+
+        if let Some((rate, bucket)) = self.rate.as_ref() {
+            while let Some(wait) = Self::take_token(rate, bucket) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+
+        let permit = match self.in_flight.as_ref() {
+            // The semaphore is never closed
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+
+        self.record(start.elapsed());
+
+        permit
+    }
+
+    // Takes a token from the bucket, or returns how long
+    // to wait until one is available
+    fn take_token(rate: &RateLimit, bucket: &Mutex<Bucket>) -> Option<Duration> {
+        let mut bucket = bucket.lock().unwrap();
+        let now = Instant::now();
+
+        let interval = rate.interval().as_secs_f64();
+        let refilled = match interval > 0.0 {
+            true => (now - bucket.refilled_at).as_secs_f64() / interval,
+            false => f64::INFINITY,
+        };
+
+        bucket.tokens = (bucket.tokens + refilled).min(rate.burst as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return None;
+        }
+
+        Some(Duration::from_secs_f64((1.0 - bucket.tokens) * interval))
+    }
+
+    fn record(&self, wait: Duration) {
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics.requests += 1;
+
+        // Ignore the time it takes to merely go through
+        // the limiter when nothing had to wait
+        if wait >= Duration::from_millis(1) {
+            metrics.throttled += 1;
+            metrics.total_wait += wait;
+            metrics.max_wait = metrics.max_wait.max(wait);
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> LimiterMetrics {
+        *self.metrics.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use reqwest::{StatusCode, Url};
+
+    use super::{Limiter, RateLimit};
+    use crate::{HttpRequest, HttpResponse, Lufa, Result, Transport};
+
+    // A transport that takes a while to respond and keeps
+    // track of how many requests it served concurrently
+    #[derive(Debug, Default)]
+    struct SlowTransport {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Transport for SlowTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(HttpResponse {
+                status: StatusCode::OK,
+                url: request.url,
+                headers: Default::default(),
+                body: Vec::new(),
+            })
+        }
+
+        fn cookies(&self, _url: &Url) -> Option<String> {
+            None
+        }
+
+        fn add_cookie(&self, _url: &Url, _cookie: &str) {}
+    }
+
+    #[tokio::test]
+    async fn test_max_in_flight() {
+        let transport = Arc::new(SlowTransport::default());
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .max_in_flight(2)
+            .build()
+            .unwrap();
+
+        let calls: Vec<_> = (0..6)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client._get("/logout").await })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap().unwrap();
+        }
+
+        assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+
+        let metrics = client.limiter_metrics();
+        assert_eq!(metrics.requests, 6);
+        assert!(metrics.throttled > 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let client = Lufa::builder()
+            .transport(SlowTransport::default())
+            .rate_limit(RateLimit::new(1, Duration::from_millis(50)).burst(2))
+            .build()
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        for _ in 0..4 {
+            client._get("/logout").await.unwrap();
+        }
+
+        // The first two requests are a burst, the next ones
+        // wait for the bucket to refill
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        let metrics = client.limiter_metrics();
+        assert_eq!(metrics.requests, 4);
+        assert!(metrics.total_wait >= metrics.max_wait);
+        assert!(metrics.max_wait > Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_frees_in_flight() {
+        let limiter = Arc::new(Limiter::new(
+            Some(RateLimit::new(1, Duration::from_millis(100))),
+            Some(1),
+        ));
+        drop(limiter.acquire().await);
+
+        // A request waiting for a token does not hold on to
+        // an in-flight slot in the meantime
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                let _permit = limiter.acquire().await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let semaphore = limiter.in_flight.as_ref().unwrap();
+        assert!(!waiting.is_finished());
+        assert_eq!(semaphore.available_permits(), 1);
+
+        waiting.await.unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashMap, fmt, future::Future, hash::Hash, sync::RwLock};
+
+use tokio::task::JoinSet;
+
+use crate::{
+    error::SharedPoolSettingSnafu, CredentialProvider, Lufa, LufaBuilder, Result, Session,
+};
+
+/// A set of clients for several Lufa accounts, keyed by an
+/// account ID of the caller's choosing.
+///
+/// Every account gets its own session, and logs in lazily
+/// through its credential provider on its first call. All
+/// accounts share the rate limit and in-flight request limit
+/// of the builder the pool is created from.
+///
+/// The builder must not have a transport, cassette to record
+/// or session set, as the accounts would then share them.
+///
+/// # Examples
+///
+/// ```
+/// use lufa::{Credentials, Lufa, LufaPool, RateLimit, Result};
+///
+/// async fn household_credits() -> Result<()> {
+///   let pool = LufaPool::new(Lufa::builder().rate_limit(RateLimit::per_second(5)))?;
+///   pool.add("alice", Credentials::new("alice@example.com", "passw0rd"))?;
+///   pool.add("bob", Credentials::new("bob@example.com", "hunter2"))?;
+///
+///   let profiles = pool
+///     .run_all(|client| async move { client.profile().get().await })
+///     .await;
+///
+///   for (account, profile) in profiles {
+///     match profile {
+///       Ok(profile) => println!("{}: ${:?}", account, profile.user_credits),
+///       Err(e) => println!("{}: {}", account, e),
+///     }
+///   }
+///
+///   Ok(())
+/// }
+/// ```
+pub struct LufaPool<K> {
+    template: LufaBuilder,
+    clients: RwLock<HashMap<K, Lufa>>,
+}
+
+impl<K: fmt::Debug> fmt::Debug for LufaPool<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LufaPool")
+            .field("template", &self.template)
+            .field("clients", &self.clients)
+            .finish()
+    }
+}
+
+impl<K> LufaPool<K>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+{
+    /// Creates an empty pool whose clients are built
+    /// from the given builder.
+    ///
+    /// Fails if the builder has a transport, cassette to
+    /// record or session set.
+    pub fn new(template: LufaBuilder) -> Result<Self> {
+        if let Some(setting) = template._shared_setting() {
+            return SharedPoolSettingSnafu { setting }.fail();
+        }
+
+        Ok(Self {
+            template: template._share_limiter(),
+            clients: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Adds an account that logs in through the given
+    /// provider, replacing any account with the same key.
+    pub fn add<P: CredentialProvider + 'static>(&self, key: K, credentials: P) -> Result<()> {
+        let client = self.template.clone().credentials(credentials).build()?;
+        self.insert(key, client);
+        Ok(())
+    }
+
+    /// Adds an account from a previously exported session,
+    /// which logs in again through the given provider once
+    /// the session expires.
+    pub fn add_session<P: CredentialProvider + 'static>(
+        &self,
+        key: K,
+        session: Session,
+        credentials: P,
+    ) -> Result<()> {
+        let client = self
+            .template
+            .clone()
+            .session(session)
+            .credentials(credentials)
+            .build()?;
+
+        self.insert(key, client);
+        Ok(())
+    }
+
+    /// Removes an account, returning its client.
+    pub fn remove(&self, key: &K) -> Option<Lufa> {
+        self.clients.write().unwrap().remove(key)
+    }
+
+    /// The client of an account.
+    pub fn get(&self, key: &K) -> Option<Lufa> {
+        self.clients.read().unwrap().get(key).cloned()
+    }
+
+    /// The keys of all accounts.
+    pub fn keys(&self) -> Vec<K> {
+        self.clients.read().unwrap().keys().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.clients.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clients.read().unwrap().is_empty()
+    }
+
+    /// Runs the same call for every account concurrently,
+    /// returning each account's result.
+    pub async fn run_all<F, Fut, T>(&self, call: F) -> HashMap<K, Result<T>>
+    where
+        F: Fn(Lufa) -> Fut,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut calls = JoinSet::new();
+        for (key, client) in self.clients.read().unwrap().iter() {
+            let key = key.clone();
+            let call = call(client.clone());
+            calls.spawn(async move { (key, call.await) });
+        }
+
+        let mut results = HashMap::new();
+        while let Some(joined) = calls.join_next().await {
+            match joined {
+                Ok((key, result)) => {
+                    results.insert(key, result);
+                }
+                Err(e) => std::panic::resume_unwind(e.into_panic()),
+            }
+        }
+
+        results
+    }
+
+    fn insert(&self, key: K, client: Lufa) {
+        self.clients.write().unwrap().insert(key, client);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lufa_mock::{MockServer, EMAIL, PASSWORD};
+
+    use super::LufaPool;
+    use crate::{Credentials, Error, Lufa, LufaBuilder, LufaError, MemoryTransport};
+
+    #[tokio::test]
+    async fn test_run_all() {
+        let server = MockServer::start().await.unwrap();
+        let pool = LufaPool::new(Lufa::builder().base_url(server.url()).max_in_flight(1)).unwrap();
+
+        pool.add(1, Credentials::new(EMAIL, PASSWORD)).unwrap();
+        pool.add(2, Credentials::new(EMAIL, PASSWORD)).unwrap();
+        pool.add(3, Credentials::new(EMAIL, "wrong")).unwrap();
+        assert_eq!(pool.len(), 3);
+
+        let results = pool
+            .run_all(|client| async move { client.profile().get().await })
+            .await;
+
+        assert!(results[&1].is_ok());
+        assert!(results[&2].is_ok());
+        assert!(matches!(
+            results[&3].as_ref().unwrap_err().lufa_error(),
+            Some(LufaError::InvalidCredentials { .. })
+        ));
+
+        // Each account has a session of its own, and
+        // all of them went through the same limiter
+        assert_eq!(server.active_sessions(), 2);
+        assert_eq!(pool.get(&1).unwrap().limiter_metrics().requests, 5);
+
+        assert!(pool.remove(&3).is_some());
+        assert_eq!(pool.keys().len(), 2);
+    }
+
+    #[test]
+    fn test_shared_settings() {
+        let shared = |builder: LufaBuilder| match LufaPool::<u8>::new(builder) {
+            Err(Error::SharedPoolSetting { setting, .. }) => Some(setting),
+            _ => None,
+        };
+
+        assert_eq!(
+            shared(Lufa::builder().transport(MemoryTransport::new())),
+            Some("transport")
+        );
+        assert_eq!(shared(Lufa::builder().record("pool.json")), Some("record"));
+        assert_eq!(shared(Lufa::builder()), None);
+    }
+}
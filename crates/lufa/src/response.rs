@@ -0,0 +1,177 @@
+use serde::{de::DeserializeOwned, Deserialize};
+use snafu::IntoError;
+
+use crate::{
+    error::{LufaError, LufaSnafu, Result},
+    models, HttpResponse, Lufa,
+};
+
+// The maximum number of characters of a response body
+// that is kept in a `LufaError::HttpStatus`
+const BODY_EXCERPT_LEN: usize = 256;
+
+// The status fields that the API sends alongside
+// most payloads, whether or not they are wrapped
+// in an `ApiResponse`
+#[derive(Deserialize)]
+struct ApiStatus {
+    #[serde(default)]
+    success: Option<bool>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl Lufa {
+    // Reads the body of a response, rejecting any
+    // non-successful HTTP status
+    pub(crate) fn _read_body(endpoint: &str, response: HttpResponse) -> Result<String> {
+        Self::_check_status(endpoint, &response)?;
+        Ok(response.text())
+    }
+
+    // Rejects any non-successful HTTP status
+    pub(crate) fn _check_status(endpoint: &str, response: &HttpResponse) -> Result<()> {
+        if !response.status.is_success() {
+            return Err(LufaSnafu.into_error(LufaError::HttpStatus {
+                endpoint: endpoint.to_string(),
+                status: response.status,
+                body_excerpt: excerpt(&response.text()),
+            }));
+        }
+
+        Ok(())
+    }
+
+    // Parses a response whose payload is the model itself
+    pub(crate) fn _parse<T: DeserializeOwned>(endpoint: &str, response: HttpResponse) -> Result<T> {
+        let raw = Self::_read_body(endpoint, response)?;
+        Self::_parse_body(endpoint, raw)
+    }
+
+    // Parses a response whose payload is wrapped
+    // in an `ApiResponse`
+    pub(crate) fn _parse_api<T: DeserializeOwned>(
+        endpoint: &str,
+        response: HttpResponse,
+    ) -> Result<T> {
+        let raw = Self::_read_body(endpoint, response)?;
+        Self::_parse_api_body(endpoint, raw)
+    }
+
+    pub(crate) fn _parse_body<T: DeserializeOwned>(endpoint: &str, raw: String) -> Result<T> {
+        Self::_check_api_status(endpoint, &raw)?;
+
+        match serde_json::from_str::<T>(&raw) {
+            Ok(data) => Ok(data),
+            Err(e) => Err(LufaSnafu.into_error(LufaError::UnexpectedResponse {
+                endpoint: endpoint.to_string(),
+                reason: e.to_string(),
+                raw,
+            })),
+        }
+    }
+
+    pub(crate) fn _parse_api_body<T: DeserializeOwned>(endpoint: &str, raw: String) -> Result<T> {
+        let response: models::ApiResponse<T> = Self::_parse_body(endpoint, raw.clone())?;
+
+        response.data.ok_or_else(|| {
+            LufaSnafu.into_error(LufaError::UnexpectedResponse {
+                endpoint: endpoint.to_string(),
+                reason: "missing data".to_string(),
+                raw,
+            })
+        })
+    }
+
+    // Surfaces the server's message when it reports that
+    // the call failed, before attempting to parse the
+    // payload itself
+    fn _check_api_status(endpoint: &str, raw: &str) -> Result<()> {
+        match serde_json::from_str::<ApiStatus>(raw) {
+            Ok(ApiStatus {
+                success: Some(false),
+                message,
+            }) => Err(LufaSnafu.into_error(LufaError::ApiFailure {
+                endpoint: endpoint.to_string(),
+                message: message.unwrap_or_default(),
+            })),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Truncates a response body for use in error messages
+fn excerpt(body: &str) -> String {
+    match body.char_indices().nth(BODY_EXCERPT_LEN) {
+        Some((idx, _)) => format!("{}…", &body[..idx]),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::excerpt;
+    use crate::{Error, Lufa, LufaError};
+
+    #[derive(Deserialize, Debug)]
+    struct Payload {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    fn lufa_error<T>(res: Result<T, Error>) -> LufaError {
+        match res {
+            Err(e) => e.lufa_error().cloned().unwrap(),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_ok() {
+        let raw = r#"{ "success": true, "data": { "name": "Bob" } }"#.to_string();
+        assert!(Lufa::_parse_api_body::<Payload>("/test", raw).is_ok());
+
+        let raw = r#"{ "success": true, "name": "Bob" }"#.to_string();
+        assert!(Lufa::_parse_body::<Payload>("/test", raw).is_ok());
+    }
+
+    #[test]
+    fn test_parse_api_failure() {
+        let raw = r#"{ "success": false, "data": [], "message": "Invalid user" }"#.to_string();
+
+        match lufa_error(Lufa::_parse_api_body::<Payload>("/test", raw)) {
+            LufaError::ApiFailure { endpoint, message } => {
+                assert_eq!(endpoint, "/test");
+                assert_eq!(message, "Invalid user");
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_parse_unexpected() {
+        let raw = "<html>Login</html>".to_string();
+        match lufa_error(Lufa::_parse_body::<Payload>("/test", raw)) {
+            e @ LufaError::UnexpectedResponse { .. } => {
+                assert_eq!(e.raw(), Some("<html>Login</html>"))
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let raw = r#"{ "success": true, "data": null }"#.to_string();
+        match lufa_error(Lufa::_parse_api_body::<Payload>("/test", raw)) {
+            LufaError::UnexpectedResponse { reason, .. } => assert_eq!(reason, "missing data"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("short"), "short");
+
+        let long = "é".repeat(300);
+        assert_eq!(excerpt(&long).chars().count(), 257);
+    }
+}
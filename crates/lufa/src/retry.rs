@@ -0,0 +1,361 @@
+use std::time::{Duration, SystemTime};
+
+use reqwest::{header::RETRY_AFTER, StatusCode};
+
+use crate::{
+    trace::{trace_event, trace_record},
+    Error, HttpRequest, HttpResponse, Lufa, Result,
+};
+
+/// Controls how the client retries requests that failed
+/// for transient reasons, such as a timeout or a `502`.
+///
+/// By default, up to 3 attempts are made with exponential
+/// backoff and jitter, waiting at least as long as asked by
+/// a `Retry-After` header, and only requests that are safe to
+/// send twice are retried: `GET`s and the `POST`s that only
+/// read data. Logging in and other `POST`s are not retried
+/// unless [`RetryPolicy::retry_non_idempotent`] is set.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use lufa::{Lufa, Result, RetryPolicy};
+///
+/// fn build_client() -> Result<Lufa> {
+///   Lufa::builder()
+///     .retry(
+///       RetryPolicy::default()
+///         .max_attempts(5)
+///         .initial_backoff(Duration::from_millis(500))
+///         .max_backoff(Duration::from_secs(10)),
+///     )
+///     .build()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<StatusCode>,
+    retry_timeouts: bool,
+    retry_connect_errors: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+            retryable_statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_timeouts: true,
+            retry_connect_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts, including the
+    /// first one. Values below 1 are treated as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry. The delay
+    /// doubles after every attempt.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets whether delays are randomized between zero and
+    /// the computed backoff, so that clients failing at the
+    /// same time do not retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the HTTP statuses that are retried.
+    pub fn retryable_statuses<S: IntoIterator<Item = StatusCode>>(mut self, statuses: S) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Sets whether requests that timed out are retried.
+    pub fn retry_timeouts(mut self, retry: bool) -> Self {
+        self.retry_timeouts = retry;
+        self
+    }
+
+    /// Sets whether requests that failed to connect are retried.
+    pub fn retry_connect_errors(mut self, retry: bool) -> Self {
+        self.retry_connect_errors = retry;
+        self
+    }
+
+    /// Sets whether requests that are not safe to send twice,
+    /// such as logging in, are retried as well.
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    // The delay before the given retry, starting at 1
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        match self.jitter {
+            true => backoff.mul_f64(fastrand::f64()),
+            false => backoff,
+        }
+    }
+
+    // The delay before the given retry of a result. A
+    // response asking to be retried later, e.g. a `429`,
+    // is waited for at least as long as it asks, up to
+    // the maximum backoff.
+    fn delay(&self, retry: u32, result: &Result<HttpResponse>) -> Duration {
+        let backoff = self.backoff(retry);
+
+        match result.as_ref().ok().and_then(retry_after) {
+            Some(after) => backoff.max(after.min(self.max_backoff)),
+            None => backoff,
+        }
+    }
+
+    fn is_retryable(&self, result: &Result<HttpResponse>) -> bool {
+        match result {
+            Ok(response) => self.retryable_statuses.contains(&response.status),
+            Err(Error::Reqwest { source, .. }) => {
+                (self.retry_timeouts && source.is_timeout())
+                    || (self.retry_connect_errors && source.is_connect())
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+impl Lufa {
+    // Sends a request, retrying it according to the
+    // client's retry policy
+    pub(crate) async fn _send_with_retry(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let policy = &self.retry;
+        let may_retry = request.idempotent || policy.retry_non_idempotent;
+
+        let mut attempt = 1;
+        loop {
+            let result = self._send(request.clone()).await;
+
+            if !may_retry || attempt >= policy.max_attempts || !policy.is_retryable(&result) {
+                trace_record!("retries", attempt - 1);
+                return result;
+            }
+
+            trace_event!(warn, attempt, "retrying request");
+
+            tokio::time::sleep(policy.delay(attempt, &result)).await;
+            attempt += 1;
+        }
+    }
+}
+
+// The delay asked for by the `Retry-After` header of a
+// response, given either in seconds or as an HTTP date
+fn retry_after(response: &HttpResponse) -> Option<Duration> {
+    let value = response.headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let at = SystemTime::from(date);
+    Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::{
+        header::{HeaderValue, RETRY_AFTER},
+        Method, StatusCode,
+    };
+
+    use super::RetryPolicy;
+    use crate::{HttpResponse, Lufa, MemoryResponse, MemoryTransport};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::default().initial_backoff(Duration::ZERO)
+    }
+
+    fn transport(method: Method, path: &str) -> MemoryTransport {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            method.clone(),
+            path,
+            MemoryResponse::new(StatusCode::BAD_GATEWAY),
+        );
+        transport.respond(method, path, MemoryResponse::ok().body("{}"));
+        transport
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+
+        let policy = policy.jitter(true);
+        assert!(policy.backoff(2) <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = RetryPolicy::default()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(5))
+            .jitter(false);
+
+        let response = |retry_after: Option<&'static str>| {
+            let mut response = HttpResponse {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                url: "http://lufa.test/en/orders".parse().unwrap(),
+                headers: Default::default(),
+                body: Vec::new(),
+            };
+            if let Some(value) = retry_after {
+                response
+                    .headers
+                    .insert(RETRY_AFTER, HeaderValue::from_static(value));
+            }
+            Ok(response)
+        };
+
+        assert_eq!(policy.delay(1, &response(None)), Duration::from_millis(100));
+        assert_eq!(
+            policy.delay(1, &response(Some("2"))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.delay(1, &response(Some("60"))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.delay(1, &response(Some("Sun, 06 Nov 1994 08:49:37 GMT"))),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.delay(1, &response(Some("soon"))),
+            Duration::from_millis(100)
+        );
+
+        // A shorter delay than the backoff is not waited for
+        assert_eq!(
+            policy.delay(4, &response(Some("0"))),
+            Duration::from_millis(800)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_idempotent() {
+        let transport = transport(Method::GET, "/superMarket/GetUserOrderDetails");
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .retry(policy())
+            .build()
+            .unwrap();
+
+        let response = client
+            ._get("/superMarket/GetUserOrderDetails")
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_non_idempotent() {
+        let transport = transport(Method::POST, "/orders/cancel");
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .retry(policy())
+            .build()
+            .unwrap();
+
+        let response = client._post("/orders/cancel", "").await.unwrap();
+        assert_eq!(response.status, StatusCode::BAD_GATEWAY);
+        assert_eq!(transport.requests().len(), 1);
+
+        let transport = self::transport(Method::POST, "/orders/cancel");
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .retry(policy().retry_non_idempotent(true))
+            .build()
+            .unwrap();
+
+        let response = client._post("/orders/cancel", "").await.unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/users/profileData",
+            MemoryResponse::new(StatusCode::SERVICE_UNAVAILABLE),
+        );
+
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .retry(policy().max_attempts(4))
+            .build()
+            .unwrap();
+
+        let response = client
+            ._post_form_idempotent("/users/profileData", &[("user_id", "123456")])
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(transport.requests().len(), 4);
+    }
+}
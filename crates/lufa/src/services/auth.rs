@@ -1,9 +1,13 @@
-use reqwest::StatusCode;
+use std::time::{Duration, SystemTime};
+
+use reqwest::{Method, StatusCode};
 use snafu::IntoError;
 
 use crate::{
-    error::{LufaError, LufaSnafu, Result, SerdePhpSnafu, UrlEncodingSnafu},
-    models, Lufa, State,
+    error::{CookieSnafu, LufaError, LufaSnafu, Result},
+    models,
+    trace::trace_event,
+    HttpResponse, Lufa, LufaStateCookie, SetCookie, State,
 };
 
 #[derive(Debug, Clone)]
@@ -24,52 +28,16 @@ impl<'a> AuthService<'a> {
     ///   Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.auth.login", skip_all)
+    )]
     pub async fn login<E: AsRef<str>, P: AsRef<str>>(
         &mut self,
         email: E,
         password: P,
     ) -> Result<()> {
-        let email = email.as_ref().to_string();
-        let password = password.as_ref().to_string();
-
-        let response = self
-            .0
-            ._post_form("/login", &models::auth::LoginReqForm { email, password })
-            .await?;
-
-        // Upon successful login, a cookie, `lufaState`, is sent
-        // from the server and contains the user's ID and email
-        // (among other values)
-        let lufa_state_cookie = response
-            .cookies()
-            .find(|c| c.name() == "lufaState" && c.value() != "deleted" && c.value().len() > 0)
-            .ok_or(LufaSnafu.into_error(LufaError {
-                message: format!("failed to login"),
-            }))?;
-
-        // There's some 40-character value we don't care about,
-        // we'll just ignore that. The cookie is also urlencoded
-        // so we decode that here
-        let lufa_state_encoded = &lufa_state_cookie.value()[40..];
-        let lufa_state_decoded = urlencoding::decode(lufa_state_encoded)
-            .map_err(|e| UrlEncodingSnafu.into_error(e.into()))?;
-
-        // The `lufaState` is a PHP-serialized array -- we
-        // deserialize it with serde_php
-        let lufa_state: models::cookies::LufaState =
-            serde_php::from_bytes(lufa_state_decoded.as_bytes())
-                .map_err(|e| SerdePhpSnafu.into_error(e.into()))?;
-
-        // Modify the client's state
-        {
-            let mut state = self.0.state.write().await;
-            *state = Some(State {
-                user_id: lufa_state.0,
-                email: lufa_state.1,
-            })
-        }
-
-        Ok(())
+        self.0._login(email.as_ref(), password.as_ref()).await
     }
 
     /// Logs a user out of the Lufa API.
@@ -86,53 +54,262 @@ impl<'a> AuthService<'a> {
     ///   Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.auth.logout", skip_all)
+    )]
     pub async fn logout(&mut self) -> Result<()> {
-        let response = self.0._get("/logout").await?;
+        let response = self
+            .0
+            ._send(self.0._request(Method::GET, "/logout")?)
+            .await?;
 
-        if response.status() == StatusCode::OK {
+        if response.status == StatusCode::OK {
             // Modify the client's state
             let mut state = self.0.state.write().await;
             *state = None;
+
+            trace_event!(info, "logged out");
         }
 
         Ok(())
     }
 }
 
+impl Lufa {
+    pub(crate) async fn _login(&self, email: &str, password: &str) -> Result<()> {
+        let email = email.to_string();
+        let password = password.to_string();
+
+        // Logging in must not go through the session expiry
+        // check, as the login page is where it redirects to
+        let response = self
+            ._send(
+                self._request(Method::POST, "/login")?
+                    .form(&models::auth::LoginReqForm { email, password })?,
+            )
+            .await?;
+
+        // A failing server is not a refused login, so the
+        // status is checked before looking for the cookie
+        if !response.status.is_redirection() {
+            Self::_check_status("/login", &response)?;
+        }
+
+        // Upon successful login, a cookie, `lufaState`, is sent
+        // from the server and contains the user's ID and email
+        // (among other values)
+        let Some(lufa_state_cookie) = find_lufa_state(&response) else {
+            trace_event!(warn, "login rejected");
+            return Err(LufaSnafu.into_error(LufaError::InvalidCredentials {
+                endpoint: "/login".to_string(),
+            }));
+        };
+
+        // The cookie is signed by the server; a value we
+        // cannot make sense of is an error rather than a panic
+        let lufa_state = lufa_state_cookie
+            .value
+            .parse::<LufaStateCookie>()
+            .map_err(|e| CookieSnafu.into_error(e))?
+            .state;
+
+        trace_event!(
+            info,
+            user = %crate::trace::user_hash(&self.trace_key, &lufa_state.0),
+            "logged in"
+        );
+
+        // The cookie's lifetime is given by its attributes, or
+        // else by the duration stored in the state itself
+        let logged_in_at = SystemTime::now();
+        let expires_at = lufa_state_cookie.expires_at(logged_in_at).or_else(|| {
+            let duration = u64::try_from(lufa_state.2).ok().filter(|d| *d > 0)?;
+            Some(logged_in_at + Duration::from_secs(duration))
+        });
+
+        // Modify the client's state
+        {
+            let mut state = self.state.write().await;
+            *state = Some(State {
+                user_id: lufa_state.0.as_str().into(),
+                email: lufa_state.1.clone(),
+                lufa_state,
+                logged_in_at: Some(logged_in_at),
+                expires_at,
+            })
+        }
+
+        Ok(())
+    }
+}
+
+// Finds the `lufaState` cookie set by a response, unless the
+// response removes it. Malformed cookies are skipped.
+fn find_lufa_state(response: &HttpResponse) -> Option<SetCookie> {
+    response
+        .set_cookies()
+        .filter_map(|header| SetCookie::parse(header).ok())
+        .find(|cookie| cookie.name == "lufaState" && !cookie.is_removal())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{Language, Lufa};
+    use lufa_mock::{MockServer, EMAIL, PASSWORD};
+    use reqwest::{Method, StatusCode};
+
+    use crate::{CookieError, Error, Lufa, LufaError, MemoryResponse, MemoryTransport};
 
     #[tokio::test]
-    async fn test_login_ok() {
-        let client = Lufa::new(Language::English).unwrap();
+    async fn test_login_parses_state() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::logged_in("123456", "bob@example.com"),
+        );
+
+        let client = Lufa::builder()
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        client
+            .auth()
+            .login("bob@example.com", "passw0rd")
+            .await
+            .unwrap();
+        assert!(client.is_logged_in().await);
+        assert_eq!(client.user_id("/").await.unwrap(), "123456");
+
+        let state = client.state.read().await.clone().unwrap();
+        assert_eq!(state.email, "bob@example.com");
+        assert_eq!(state.lufa_state.3.first_name, "Bob");
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.url.path(), "/en/login");
+        let body = String::from_utf8(request.body.clone().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            "LoginForm%5Buser_email%5D=bob%40example.com&LoginForm%5Bpassword%5D=passw0rd"
+        );
+    }
 
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+    #[tokio::test]
+    async fn test_login_invalid_credentials() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::ok().set_cookie("lufaState=deleted; Path=/"),
+        );
+
+        let client = Lufa::builder().transport(transport).build().unwrap();
+        let res = client.auth().login("bob@example.com", "wrong").await;
+
+        assert!(matches!(
+            res.unwrap_err().lufa_error(),
+            Some(LufaError::InvalidCredentials { .. })
+        ));
+        assert!(!client.is_logged_in().await);
+    }
 
-        let res = client.auth().login(&email, &password).await;
+    #[tokio::test]
+    async fn test_login_server_failure() {
+        for status in [
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            let transport = MemoryTransport::new();
+            transport.respond(
+                Method::POST,
+                "/login",
+                MemoryResponse::new(status).body("<html>Oops</html>"),
+            );
+
+            let client = Lufa::builder().transport(transport).build().unwrap();
+            let res = client.auth().login("bob@example.com", "passw0rd").await;
+
+            match res.unwrap_err().lufa_error() {
+                Some(LufaError::HttpStatus {
+                    endpoint,
+                    status: actual,
+                    body_excerpt,
+                }) => {
+                    assert_eq!(endpoint, "/login");
+                    assert_eq!(*actual, status);
+                    assert_eq!(body_excerpt, "<html>Oops</html>");
+                }
+                e => panic!("unexpected error: {:?}", e),
+            }
+            assert!(!client.is_logged_in().await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_malformed_state() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::ok()
+                .set_cookie("HttpOnly")
+                .set_cookie("lufaState=not-signed; Path=/"),
+        );
+
+        let client = Lufa::builder().transport(transport).build().unwrap();
+        let res = client.auth().login("bob@example.com", "passw0rd").await;
+
+        assert!(matches!(
+            res.unwrap_err(),
+            Error::Cookie {
+                source: CookieError::Unsigned { .. },
+                ..
+            }
+        ));
+        assert!(!client.is_logged_in().await);
+    }
+
+    #[tokio::test]
+    async fn test_login_ok() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let state = client.state.read().await;
         assert!(state.is_some());
 
         let state = state.clone().unwrap();
-        assert_eq!(email, state.email);
+        assert_eq!(EMAIL, state.email);
     }
 
     #[tokio::test]
     async fn test_logout_ok() {
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        let res = client.auth().login(&email, &password).await;
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let res = client.auth().logout().await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, false);
+        assert!(!client.is_logged_in().await);
+        assert_eq!(server.active_sessions(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+
+        let res = client.auth().login(EMAIL, "wrong").await;
+        assert!(matches!(
+            res.unwrap_err().lufa_error(),
+            Some(LufaError::InvalidCredentials { .. })
+        ));
+        assert!(!client.is_logged_in().await);
     }
 }
@@ -1,12 +1,8 @@
 use std::collections::HashMap;
 
+use crate::{models, Lufa, Result};
 use lufa_models::billing::Card;
-use snafu::IntoError;
-
-use crate::{
-    error::{LufaError, LufaSnafu, ReqwestSnafu},
-    models, Lufa, Result,
-};
+use serde::de::DeserializeOwned;
 
 #[derive(Debug, Clone)]
 pub struct BillingService<'a>(pub(crate) &'a Lufa);
@@ -18,24 +14,21 @@ impl<'a> BillingService<'a> {
     /// This function is not to be used by client code,
     /// thus it is not marked as public.
     /// ```
-    async fn get_billing_data(&self) -> Result<models::billing::BillingData> {
-        self.0.guard_logged_in().await?;
+    async fn get_billing_data<T: DeserializeOwned>(&self) -> Result<T> {
+        let endpoint = "/users/billingData";
+        self.0.guard_logged_in(endpoint).await?;
 
-        self.0
-            ._post_form(
-                "/users/billingData",
+        let response = self
+            .0
+            ._post_form_idempotent(
+                endpoint,
                 &models::PerUserForm {
-                    user_id: self.0.user_id().await?,
+                    user_id: self.0.user_id(endpoint).await?,
                 },
             )
-            .await?
-            .json::<models::ApiResponse<models::billing::BillingData>>()
-            .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?
-            .data
-            .ok_or(LufaSnafu.into_error(LufaError {
-                message: format!("failed to get billing data"),
-            }))
+            .await?;
+
+        Lufa::_parse_api(endpoint, response)
     }
 
     /// Retrieves the configured payment cards for
@@ -59,8 +52,24 @@ impl<'a> BillingService<'a> {
     ///   Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.billing.get_cards", skip_all)
+    )]
     pub async fn get_cards(&self) -> Result<HashMap<usize, Card>> {
-        self.get_billing_data().await.map(|bd| bd.cards)
+        self.get_billing_data::<models::billing::BillingData>()
+            .await
+            .map(|bd| bd.cards)
+    }
+
+    /// Retrieves the configured payment cards as raw JSON,
+    /// for when they no longer parse into [`Card`]s.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.billing.get_cards_raw", skip_all)
+    )]
+    pub async fn get_cards_raw(&self) -> Result<serde_json::Value> {
+        self.get_billing_data_field("cards").await
     }
 
     /// Retrieves the list of completed transactions for
@@ -89,40 +98,73 @@ impl<'a> BillingService<'a> {
     ///   Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.billing.get_transactions", skip_all)
+    )]
     pub async fn get_transactions(&self) -> Result<Vec<models::billing::Transaction>> {
-        self.get_billing_data().await.map(|bd| bd.transactions)
+        self.get_billing_data::<models::billing::BillingData>()
+            .await
+            .map(|bd| bd.transactions)
+    }
+
+    /// Retrieves the list of completed transactions as raw
+    /// JSON, for when they no longer parse into
+    /// [`Transaction`](models::billing::Transaction)s.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.billing.get_transactions_raw", skip_all)
+    )]
+    pub async fn get_transactions_raw(&self) -> Result<serde_json::Value> {
+        self.get_billing_data_field("transactions").await
+    }
+
+    // Retrieves a single field of the billing data as raw
+    // JSON, or `null` if the server did not send it
+    async fn get_billing_data_field(&self, field: &str) -> Result<serde_json::Value> {
+        let mut data: serde_json::Value = self.get_billing_data().await?;
+        Ok(data
+            .get_mut(field)
+            .map(serde_json::Value::take)
+            .unwrap_or_default())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Language, Lufa};
+    use lufa_mock::{MockServer, EMAIL, PASSWORD};
+
+    use crate::Lufa;
 
     #[tokio::test]
     async fn test_get_cards_ok() {
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        let res = client.auth().login(&email, &password).await;
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let cards = client.billing().get_cards().await;
         assert!(cards.is_ok());
+
+        let raw = client.billing().get_cards_raw().await.unwrap();
+        assert_eq!(raw.as_object().unwrap().len(), cards.unwrap().len());
     }
 
     #[tokio::test]
     async fn test_get_transactions_ok() {
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        let res = client.auth().login(&email, &password).await;
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let txs = client.billing().get_transactions().await;
         assert!(txs.is_ok());
+
+        let raw = client.billing().get_transactions_raw().await.unwrap();
+        assert_eq!(raw.as_array().unwrap().len(), txs.unwrap().len());
     }
 }
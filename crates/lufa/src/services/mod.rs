@@ -8,4 +8,7 @@ mod profile;
 pub use profile::*;
 
 mod orders;
-pub use orders::*;
\ No newline at end of file
+pub use orders::*;
+
+mod raw;
+pub use raw::*;
\ No newline at end of file
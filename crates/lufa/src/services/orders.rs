@@ -1,83 +1,162 @@
-use lufa_models::PerOrderForm;
-use snafu::IntoError;
+use lufa_models::{OrderId, PerOrderForm};
+use serde::de::DeserializeOwned;
 
-use crate::{models, Lufa, ReqwestSnafu, Result};
+use crate::{models, Lufa, Result};
 
 #[derive(Debug, Clone)]
 pub struct OrdersService<'a>(pub(crate) &'a Lufa);
 
 impl<'a> OrdersService<'a> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.orders.get_active", skip_all)
+    )]
     pub async fn get_active(&self) -> Result<Option<models::orders::Order>> {
-        self.0.guard_logged_in().await?;
-
-        self.0
-            ._get("/superMarket/GetUserOrderDetails")
-            .await?
-            .json::<models::orders::Order>()
-            .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))
-            .map(Some)
+        self.fetch_active().await.map(Some)
     }
 
-    pub async fn track<O: AsRef<str>>(&self, order_id: O) -> Result<models::orders::OrderTracking> {
-        self.0.guard_logged_in().await?;
+    /// Retrieves the active order as raw JSON, for when it
+    /// no longer parses into an [`Order`](models::orders::Order).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.orders.get_active_raw", skip_all)
+    )]
+    pub async fn get_active_raw(&self) -> Result<serde_json::Value> {
+        self.fetch_active().await
+    }
+
+    /// Retrieves the tracking information of an order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result};
+    ///
+    /// async fn track_my_order() -> Result<()> {
+    ///   let client = Lufa::new(Language::English)?;
+    ///   client.auth().login("bob@example.com", "passw0rd").await?;
+    ///
+    ///   if let Some(order) = client.orders().get_active().await? {
+    ///     let tracking = client.orders().track(&order.id).await?;
+    ///     println!("{}: {}", order.id, tracking.description);
+    ///   }
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "lufa.orders.track",
+            skip_all,
+            fields(order_id = order_id.as_str())
+        )
+    )]
+    pub async fn track(&self, order_id: &OrderId) -> Result<models::orders::OrderTracking> {
+        self.fetch_tracking(order_id).await
+    }
+
+    /// Retrieves the tracking information of an order as
+    /// raw JSON, for when it no longer parses into an
+    /// [`OrderTracking`](models::orders::OrderTracking).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "lufa.orders.track_raw",
+            skip_all,
+            fields(order_id = order_id.as_str())
+        )
+    )]
+    pub async fn track_raw(&self, order_id: &OrderId) -> Result<serde_json::Value> {
+        self.fetch_tracking(order_id).await
+    }
+
+    async fn fetch_active<T: DeserializeOwned>(&self) -> Result<T> {
+        let endpoint = "/superMarket/GetUserOrderDetails";
+        self.0.guard_logged_in(endpoint).await?;
+
+        let response = self.0._get(endpoint).await?;
+
+        Lufa::_parse(endpoint, response)
+    }
 
-        self.0
-            ._post_form(
-                "/orders/getTrackOrderData",
+    async fn fetch_tracking<T: DeserializeOwned>(&self, order_id: &OrderId) -> Result<T> {
+        let endpoint = "/orders/getTrackOrderData";
+        self.0.guard_logged_in(endpoint).await?;
+
+        let response = self
+            .0
+            ._post_form_idempotent(
+                endpoint,
                 &PerOrderForm {
-                    order_id: order_id.as_ref().to_string(),
+                    order_id: order_id.clone(),
                 },
             )
-            .await?
-            .json::<models::orders::OrderTracking>()
-            .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))
+            .await?;
+
+        Lufa::_parse(endpoint, response)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use lufa_mock::{MockServer, EMAIL, ORDER_ID, PASSWORD};
     use snafu::IntoError;
 
     use crate::{Error, Language, Lufa, LufaError, LufaSnafu};
 
     #[tokio::test]
     async fn test_get_active_order() {
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        let res = client.auth().login(&email, &password).await;
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let order = client.orders().get_active().await;
         assert!(order.is_ok());
+
+        let raw = client.orders().get_active_raw().await.unwrap();
+        assert_eq!(raw["orderId"], ORDER_ID);
     }
 
     #[tokio::test]
     async fn test_get_tracking() -> Result<(), Error> {
         env_logger::init();
 
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        client.auth().login(&email, &password).await?;
-        let order = client
-            .orders()
-            .get_active()
-            .await?
-            .ok_or(LufaSnafu.into_error(LufaError {
-                message: format!("failed to get order"),
-            }))?;
+        client.auth().login(EMAIL, PASSWORD).await?;
+        let order = client.orders().get_active().await?.ok_or_else(|| {
+            LufaSnafu.into_error(LufaError::ApiFailure {
+                endpoint: "/superMarket/GetUserOrderDetails".to_string(),
+                message: "no active order".to_string(),
+            })
+        })?;
 
-        let tracking = client.orders().track(order.id).await;
+        let tracking = client.orders().track(&order.id).await;
         assert!(tracking.is_ok());
 
+        let raw = client.orders().track_raw(&order.id).await?;
+        assert_eq!(raw["order_id"], order.id.as_str());
+
         Ok(())
     }
 
-    // https://montreal.lufa.com/en/orders/getTrackOrderData
+    #[tokio::test]
+    async fn test_get_active_order_with_language() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+        client.auth().login(EMAIL, PASSWORD).await.unwrap();
+
+        let french = client.with_language(Language::French);
+        assert_eq!(french.language(), Language::French);
+        assert_eq!(client.language(), Language::English);
+
+        let order = french.orders().get_active().await;
+        assert!(order.is_ok());
+        assert_eq!(server.active_sessions(), 1);
+    }
 }
@@ -1,9 +1,6 @@
-use snafu::IntoError;
+use serde::de::DeserializeOwned;
 
-use crate::{
-    error::{LufaError, LufaSnafu, ReqwestSnafu},
-    models, Lufa, Result,
-};
+use crate::{models, Lufa, Result};
 
 #[derive(Debug, Clone)]
 pub struct ProfileService<'a>(pub(crate) &'a Lufa);
@@ -31,42 +28,62 @@ impl<'a> ProfileService<'a> {
     ///   Ok(())
     /// }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.profile.get", skip_all)
+    )]
     pub async fn get(&self) -> Result<models::profile::Profile> {
-        self.0.guard_logged_in().await?;
+        self.fetch().await
+    }
+
+    /// Retrieves the profile of the currently logged in
+    /// user as raw JSON, for when it no longer parses into
+    /// a [`Profile`](models::profile::Profile).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.profile.get_raw", skip_all)
+    )]
+    pub async fn get_raw(&self) -> Result<serde_json::Value> {
+        self.fetch().await
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self) -> Result<T> {
+        let endpoint = "/users/profileData";
+        self.0.guard_logged_in(endpoint).await?;
 
-        self.0
-            ._post_form(
-                "/users/profileData",
+        let response = self
+            .0
+            ._post_form_idempotent(
+                endpoint,
                 &models::PerUserForm {
-                    user_id: self.0.user_id().await?,
+                    user_id: self.0.user_id(endpoint).await?,
                 },
             )
-            .await?
-            .json::<models::ApiResponse<models::profile::Profile>>()
-            .await
-            .map_err(|e| ReqwestSnafu.into_error(e.into()))?
-            .data
-            .ok_or(LufaSnafu.into_error(LufaError {
-                message: format!("failed to get profile data"),
-            }))
+            .await?;
+
+        Lufa::_parse_api(endpoint, response)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Language, Lufa};
+    use lufa_mock::{MockServer, EMAIL, PASSWORD, USER_ID};
+
+    use crate::Lufa;
 
     #[tokio::test]
     async fn test_get_profile_ok() {
-        let client = Lufa::new(Language::English).unwrap();
-        let email = std::env::var("LUFA_EMAIL").unwrap();
-        let password = std::env::var("LUFA_PASSWORD").unwrap();
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
 
-        let res = client.auth().login(&email, &password).await;
+        let res = client.auth().login(EMAIL, PASSWORD).await;
         assert!(res.is_ok());
-        assert_eq!(client.is_logged_in().await, true);
+        assert!(client.is_logged_in().await);
 
         let profile = client.profile().get().await;
         assert!(profile.is_ok());
+
+        let raw = client.profile().get_raw().await.unwrap();
+        assert_eq!(raw["user_id"], USER_ID);
     }
 }
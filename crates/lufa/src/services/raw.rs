@@ -0,0 +1,134 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Lufa, Result};
+
+/// Calls arbitrary endpoints with the client's session,
+/// returning their responses as raw JSON.
+///
+/// Requests go through the same retries, limits and session
+/// renewal as the typed services. Paths are relative to the
+/// language prefix, e.g. `/users/profileData`.
+#[derive(Debug, Clone)]
+pub struct RawService<'a>(pub(crate) &'a Lufa);
+
+impl<'a> RawService<'a> {
+    /// Sends a `GET` request to an endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result};
+    ///
+    /// async fn explore() -> Result<()> {
+    ///   let client = Lufa::new(Language::English)?;
+    ///   client.auth().login("bob@example.com", "passw0rd").await?;
+    ///
+    ///   let order = client.raw().get("/superMarket/GetUserOrderDetails").await?;
+    ///   println!("{:#}", order);
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.raw.get", skip_all, fields(path = path))
+    )]
+    pub async fn get(&self, path: &str) -> Result<Value> {
+        let response = self.0._get(path).await?;
+        Lufa::_parse(path, response)
+    }
+
+    /// Posts a urlencoded form to an endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result};
+    ///
+    /// async fn explore() -> Result<()> {
+    ///   let client = Lufa::new(Language::English)?;
+    ///   client.auth().login("bob@example.com", "passw0rd").await?;
+    ///
+    ///   let Some(session) = client.session().await else {
+    ///     return Ok(());
+    ///   };
+    ///
+    ///   let billing = client
+    ///     .raw()
+    ///     .post_form("/users/billingData", &[("user_id", session.user_id)])
+    ///     .await?;
+    ///   println!("{:#}", billing["data"]);
+    ///
+    ///   Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.raw.post_form", skip_all, fields(path = path))
+    )]
+    pub async fn post_form<Form: Serialize>(&self, path: &str, form: &Form) -> Result<Value> {
+        let response = self.0._post_form(path, form).await?;
+        Lufa::_parse(path, response)
+    }
+
+    /// Posts a JSON payload to an endpoint.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "lufa.raw.post_json", skip_all, fields(path = path))
+    )]
+    pub async fn post_json<Payload: Serialize>(
+        &self,
+        path: &str,
+        payload: &Payload,
+    ) -> Result<Value> {
+        let response = self.0._post_json(path, payload).await?;
+        Lufa::_parse(path, response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lufa_mock::{MockServer, EMAIL, ORDER_ID, PASSWORD, USER_ID};
+    use reqwest::StatusCode;
+
+    use crate::{Lufa, LufaError};
+
+    #[tokio::test]
+    async fn test_raw_calls() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+        client.auth().login(EMAIL, PASSWORD).await.unwrap();
+
+        let order = client
+            .raw()
+            .get("/superMarket/GetUserOrderDetails")
+            .await
+            .unwrap();
+        assert_eq!(order["orderId"], ORDER_ID);
+
+        let profile = client
+            .raw()
+            .post_form("/users/profileData", &[("user_id", USER_ID)])
+            .await
+            .unwrap();
+        assert_eq!(profile["success"], true);
+        assert_eq!(profile["data"]["user_id"], USER_ID);
+    }
+
+    #[tokio::test]
+    async fn test_raw_unknown_endpoint() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+        client.auth().login(EMAIL, PASSWORD).await.unwrap();
+
+        let err = client.raw().get("/undocumented").await.unwrap_err();
+        assert!(matches!(
+            err.lufa_error(),
+            Some(LufaError::HttpStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use snafu::IntoError;
+
+use crate::{
+    error::{IoSnafu, LufaError, LufaSnafu, Result, SerdeJsonSnafu},
+    models, Lufa, State,
+};
+
+/// A snapshot of an authenticated session, i.e. the cookies
+/// issued by Lufa along with the decoded `lufaState` cookie.
+///
+/// Sessions can be serialized and later restored with
+/// [`Lufa::from_session`] or [`Lufa::restore_session`] to
+/// avoid logging in again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    // The cookies set for the Lufa domain, by name
+    pub cookies: BTreeMap<String, String>,
+
+    // The decoded `lufaState` cookie
+    pub state: models::cookies::LufaState,
+
+    // When the session was opened and when it expires, if
+    // known. Sessions exported by older versions lack these.
+    #[serde(default)]
+    pub logged_in_at: Option<SystemTime>,
+    #[serde(default)]
+    pub expires_at: Option<SystemTime>,
+}
+
+impl Session {
+    /// The ID of the user this session belongs to.
+    pub fn user_id(&self) -> models::UserId {
+        self.state.0.as_str().into()
+    }
+
+    /// The email of the user this session belongs to.
+    pub fn email(&self) -> &str {
+        &self.state.1
+    }
+}
+
+impl From<&Session> for State {
+    fn from(session: &Session) -> Self {
+        State {
+            user_id: session.user_id(),
+            email: session.state.1.clone(),
+            lufa_state: session.state.clone(),
+            logged_in_at: session.logged_in_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// Information about the user a client is logged in as.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use lufa::{Lufa, Result};
+///
+/// async fn greet(client: &Lufa) -> Result<()> {
+///   if let Some(session) = client.session().await {
+///     println!("Logged in as {} ({})", session.first_name, session.email);
+///
+///     if session.expires_in().is_some_and(|left| left < Duration::from_secs(3600)) {
+///       client.auth().login("bob@example.com", "passw0rd").await?;
+///     }
+///   }
+///
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    // The user's ID, email and first name
+    pub user_id: models::UserId,
+    pub email: String,
+    pub first_name: String,
+
+    // When the `lufaState` cookie expires, if the server
+    // gave it a lifetime
+    pub expires_at: Option<SystemTime>,
+
+    // When the client logged in, if the session was not
+    // restored from one that lacks it
+    pub logged_in_at: Option<SystemTime>,
+}
+
+impl SessionInfo {
+    /// The time left until the session expires, or `None`
+    /// if its expiry is unknown. Zero once it has expired.
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_at
+            .map(|at| at.duration_since(SystemTime::now()).unwrap_or_default())
+    }
+
+    /// Whether the session is known to have expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_in() == Some(Duration::ZERO)
+    }
+}
+
+impl From<&State> for SessionInfo {
+    fn from(state: &State) -> Self {
+        SessionInfo {
+            user_id: state.user_id.clone(),
+            email: state.email.clone(),
+            first_name: state.lufa_state.3.first_name.clone(),
+            expires_at: state.expires_at,
+            logged_in_at: state.logged_in_at,
+        }
+    }
+}
+
+impl Lufa {
+    /// Creates a client against the production Lufa API
+    /// from a previously exported session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result, Session};
+    ///
+    /// async fn resume(session: Session) -> Result<()> {
+    ///   let client = Lufa::from_session(session)?;
+    ///   let profile = client.profile().get().await?;
+    ///   Ok(())
+    /// }
+    /// ```
+    pub fn from_session(session: Session) -> Result<Self> {
+        Self::builder().session(session).build()
+    }
+
+    /// Returns information about the logged in user, or
+    /// `None` if the client is not logged in.
+    pub async fn session(&self) -> Option<SessionInfo> {
+        if !self.is_logged_in().await {
+            return None;
+        }
+
+        self.state.read().await.as_ref().map(SessionInfo::from)
+    }
+
+    /// Exports the current session so that it can be
+    /// persisted and restored later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lufa::{Lufa, Language, Result, Session};
+    ///
+    /// async fn sign_in() -> Result<Session> {
+    ///   let client = Lufa::new(Language::English)?;
+    ///   client.auth().login("bob@example.com", "passw0rd").await?;
+    ///   client.export_session().await
+    /// }
+    /// ```
+    pub async fn export_session(&self) -> Result<Session> {
+        self.guard_logged_in("/").await?;
+
+        let state = self.state.read().await;
+        let state = state.clone().ok_or_else(|| {
+            LufaSnafu.into_error(LufaError::NotLoggedIn {
+                endpoint: "/".to_string(),
+            })
+        })?;
+
+        Ok(Session {
+            cookies: self._cookies().into_iter().collect(),
+            state: state.lufa_state,
+            logged_in_at: state.logged_in_at,
+            expires_at: state.expires_at,
+        })
+    }
+
+    /// Restores a previously exported session into this
+    /// client, replacing its current login state.
+    pub async fn restore_session(&self, session: &Session) -> Result<()> {
+        self._restore_cookies(session)?;
+
+        let mut state = self.state.write().await;
+        *state = Some(State::from(session));
+
+        Ok(())
+    }
+
+    pub(crate) fn _restore_cookies(&self, session: &Session) -> Result<()> {
+        let url = self._build_url("")?;
+
+        for (name, value) in session.cookies.iter() {
+            self.transport
+                .add_cookie(&url, &format!("{}={}; Path=/", name, value));
+        }
+
+        Ok(())
+    }
+}
+
+/// Persists a [`Session`] as JSON in a file on disk.
+///
+/// # Examples
+///
+/// ```
+/// use lufa::{FileSessionStore, Lufa, Language, Result};
+///
+/// async fn cached_client() -> Result<Lufa> {
+///   let store = FileSessionStore::new("/tmp/lufa-session.json");
+///
+///   if let Some(session) = store.load().await? {
+///     return Lufa::from_session(session);
+///   }
+///
+///   let client = Lufa::new(Language::English)?;
+///   client.auth().login("bob@example.com", "passw0rd").await?;
+///   store.save(&client.export_session().await?).await?;
+///
+///   Ok(client)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Loads the stored session, returning `None` if
+    /// no session has been saved yet.
+    pub async fn load(&self) -> Result<Option<Session>> {
+        let bytes = match tokio::fs::read(&self.path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(IoSnafu.into_error(e)),
+        };
+
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| SerdeJsonSnafu.into_error(e))
+    }
+
+    /// Saves the session, overwriting any previously
+    /// stored session. On Unix, the file is only readable
+    /// by its owner since it contains the session cookies.
+    pub async fn save(&self, session: &Session) -> Result<()> {
+        let bytes = serde_json::to_vec_pretty(session).map_err(|e| SerdeJsonSnafu.into_error(e))?;
+
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+
+        let mut file = options
+            .open(&self.path)
+            .await
+            .map_err(|e| IoSnafu.into_error(e))?;
+
+        tokio::io::AsyncWriteExt::write_all(&mut file, &bytes)
+            .await
+            .map_err(|e| IoSnafu.into_error(e))
+    }
+
+    /// Removes the stored session, if any.
+    pub async fn clear(&self) -> Result<()> {
+        match tokio::fs::remove_file(&self.path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(IoSnafu.into_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        time::{Duration, SystemTime},
+    };
+
+    use lufa_mock::{MockServer, EMAIL, FIRST_NAME, PASSWORD, USER_ID};
+
+    use crate::{models, FileSessionStore, Language, Lufa, Session};
+
+    fn session() -> Session {
+        Session {
+            cookies: BTreeMap::from([
+                ("PHPSESSID".to_string(), "abc123".to_string()),
+                ("lufaState".to_string(), "signed-state".to_string()),
+            ]),
+            state: models::cookies::LufaState(
+                "123456".to_string(),
+                "bob@example.com".to_string(),
+                0,
+                models::cookies::LufaStateInfo {
+                    user_email: "bob@example.com".to_string(),
+                    first_name: "Bob".to_string(),
+                },
+            ),
+            logged_in_at: None,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_not_logged_in() {
+        let client = Lufa::new(Language::English).unwrap();
+        assert!(client.export_session().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_from_session_roundtrip() {
+        let client = Lufa::from_session(session()).unwrap();
+        assert!(client.is_logged_in().await);
+        assert_eq!(client.user_id("/").await.unwrap(), "123456");
+
+        let exported = client.export_session().await.unwrap();
+        assert_eq!(exported, session());
+    }
+
+    #[tokio::test]
+    async fn test_session_info() {
+        let server = MockServer::start().await.unwrap();
+        let client = Lufa::builder().base_url(server.url()).build().unwrap();
+        assert_eq!(client.session().await, None);
+
+        client.auth().login(EMAIL, PASSWORD).await.unwrap();
+
+        let info = client.session().await.unwrap();
+        assert_eq!(info.user_id, USER_ID);
+        assert_eq!(info.email, EMAIL);
+        assert_eq!(info.first_name, FIRST_NAME);
+        assert!(!info.is_expired());
+
+        // The mock server's cookie lasts 30 days
+        let lifetime = info
+            .expires_at
+            .unwrap()
+            .duration_since(info.logged_in_at.unwrap())
+            .unwrap();
+        assert_eq!(lifetime, Duration::from_secs(2592000));
+
+        // The login time survives exporting the session
+        let exported = client.export_session().await.unwrap();
+        let restored = Lufa::from_session(exported).unwrap();
+        assert_eq!(restored.session().await, Some(info));
+    }
+
+    #[tokio::test]
+    async fn test_expired_session() {
+        let expired = Session {
+            expires_at: Some(SystemTime::now() - Duration::from_secs(1)),
+            ..session()
+        };
+
+        let client = Lufa::from_session(expired).unwrap();
+        assert!(!client.is_logged_in().await);
+        assert_eq!(client.session().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_restore_session() {
+        let client = Lufa::new(Language::French).unwrap();
+        client.restore_session(&session()).await.unwrap();
+        assert!(client.is_logged_in().await);
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let path = std::env::temp_dir().join(format!("lufa-session-{}.json", std::process::id()));
+        let store = FileSessionStore::new(&path);
+
+        assert_eq!(store.load().await.unwrap(), None);
+
+        store.save(&session()).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(session()));
+
+        store.clear().await.unwrap();
+        assert_eq!(store.load().await.unwrap(), None);
+    }
+}
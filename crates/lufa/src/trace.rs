@@ -0,0 +1,67 @@
+// Helpers for the optional `tracing` instrumentation. The
+// macros expand to nothing unless the `tracing` feature is
+// enabled, so that call sites need no `cfg` of their own.
+
+// Emits a `tracing` event, e.g.
+// `trace_event!(info, user = %hash, "logged in")`
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
+// Records the value of a field declared on the current span
+macro_rules! trace_record {
+    ($field:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record($field, $value);
+    };
+}
+
+pub(crate) use {trace_event, trace_record};
+
+// The key that user IDs are hashed with, unless one is set
+// with `LufaBuilder::trace_key`: random, and the same for
+// every client of the process
+#[cfg(feature = "tracing")]
+pub(crate) fn default_trace_key() -> [u8; 32] {
+    static KEY: std::sync::OnceLock<[u8; 32]> = std::sync::OnceLock::new();
+
+    *KEY.get_or_init(|| {
+        let mut key = [0u8; 32];
+        if getrandom::getrandom(&mut key).is_err() {
+            key.fill_with(|| fastrand::u8(..));
+        }
+        key
+    })
+}
+
+// Hashes a user ID with a secret key, so that it can be
+// correlated across traces without being recorded in the
+// clear. Without the key, the hash cannot be reversed by
+// hashing every possible user ID.
+#[cfg(feature = "tracing")]
+pub(crate) fn user_hash(key: &[u8; 32], user_id: &str) -> String {
+    let hash = blake3::keyed_hash(key, user_id.as_bytes());
+    hash.to_hex()[..16].to_string()
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::{default_trace_key, user_hash};
+
+    #[test]
+    fn test_user_hash() {
+        let key = default_trace_key();
+        assert_eq!(key, default_trace_key());
+
+        assert_eq!(user_hash(&key, "123456"), user_hash(&key, "123456"));
+        assert_ne!(user_hash(&key, "123456"), user_hash(&key, "654321"));
+        assert!(!user_hash(&key, "123456").contains("123456"));
+
+        // Hashes only match across keys if the keys do
+        assert_ne!(user_hash(&[1; 32], "123456"), user_hash(&[2; 32], "123456"));
+        assert_eq!(user_hash(&[1; 32], "123456"), user_hash(&[1; 32], "123456"));
+    }
+}
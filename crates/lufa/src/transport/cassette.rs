@@ -0,0 +1,538 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use reqwest::{
+    cookie::{CookieStore, Jar},
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, SET_COOKIE},
+    StatusCode, Url,
+};
+use serde::{Deserialize, Serialize};
+use snafu::IntoError;
+
+use crate::{
+    decode_lufa_state, encode_lufa_state,
+    error::{CassetteMissSnafu, IoSnafu, Result, SerdeJsonSnafu},
+    trace::trace_event,
+};
+
+use super::{HttpRequest, HttpResponse, Transport};
+
+// The value that replaces redacted cookies and form fields
+const REDACTED: &str = "REDACTED";
+
+// The email that replaces the user's own in `lufaState`
+const REDACTED_EMAIL: &str = "redacted@example.com";
+
+/// A recording of the requests a client sent and the
+/// responses it received, stored as JSON.
+///
+/// Cassettes are written by a [`RecordingTransport`] and
+/// served back by a [`ReplayTransport`]. The bodies of the
+/// recorded responses are kept verbatim, which makes them
+/// suitable as fixtures for the `lufa-models` tests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// A single request and the response it received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+
+    // The path and query of the URL, e.g. `/en/users/profileData`
+    pub path: String,
+
+    // The body, with credentials redacted
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+
+    // The path and query of the final URL, after
+    // following any redirects
+    pub path: String,
+
+    // The headers, with cookies redacted
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Cassette {
+    /// Loads a cassette from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = std::fs::read(path).map_err(|e| IoSnafu.into_error(e))?;
+        serde_json::from_slice(&bytes).map_err(|e| SerdeJsonSnafu.into_error(e))
+    }
+
+    /// Saves the cassette as a JSON file, overwriting
+    /// any existing file.
+    pub async fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        tokio::fs::write(path, self._to_json()?)
+            .await
+            .map_err(|e| IoSnafu.into_error(e))
+    }
+
+    fn _to_json(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(self).map_err(|e| SerdeJsonSnafu.into_error(e))
+    }
+}
+
+impl Interaction {
+    // Captures a request and its response, redacting the
+    // cookies and credentials they carry
+    fn redacted(request: &HttpRequest, response: &HttpResponse) -> Self {
+        let is_form = request
+            .headers
+            .get(CONTENT_TYPE)
+            .is_some_and(|v| v.as_bytes() == b"application/x-www-form-urlencoded");
+
+        let body = request.body.as_ref().map(|body| match is_form {
+            true => redact_form(body),
+            false => String::from_utf8_lossy(body).into_owned(),
+        });
+
+        let headers = response
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = value.to_str().unwrap_or_default();
+                let value = match *name == SET_COOKIE {
+                    true => redact_set_cookie(value),
+                    false => value.to_string(),
+                };
+
+                (name.to_string(), value)
+            })
+            .collect();
+
+        Self {
+            request: RecordedRequest {
+                method: request.method.to_string(),
+                path: path_of(&request.url),
+                body,
+            },
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                path: path_of(&response.url),
+                headers,
+                body: response.text(),
+            },
+        }
+    }
+
+    fn matches(&self, request: &HttpRequest) -> bool {
+        self.request.method == request.method.as_str() && self.request.path == path_of(&request.url)
+    }
+}
+
+// The path and query of a URL
+fn path_of(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+// Redacts the emails and passwords of a form body
+fn redact_form(body: &[u8]) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+
+    for (key, value) in url::form_urlencoded::parse(body) {
+        let lowercase = key.to_lowercase();
+        match lowercase.contains("password") || lowercase.contains("email") {
+            true => serializer.append_pair(&key, REDACTED),
+            false => serializer.append_pair(&key, &value),
+        };
+    }
+
+    serializer.finish()
+}
+
+// Redacts the value of a `Set-Cookie` header, keeping its
+// attributes. The `lufaState` cookie is rewritten rather
+// than dropped so that replayed logins still succeed.
+fn redact_set_cookie(cookie: &str) -> String {
+    let (pair, attributes) = match cookie.split_once(';') {
+        Some((pair, attributes)) => (pair, Some(attributes)),
+        None => (cookie, None),
+    };
+
+    let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+    let value = match name.trim() {
+        _ if value.is_empty() || value == "deleted" => value.to_string(),
+        "lufaState" => redact_lufa_state(value),
+        _ => REDACTED.to_string(),
+    };
+
+    match attributes {
+        Some(attributes) => format!("{}={};{}", name, value, attributes),
+        None => format!("{}={}", name, value),
+    }
+}
+
+fn redact_lufa_state(value: &str) -> String {
+    match decode_lufa_state(value) {
+        Ok(mut state) => {
+            state.1 = REDACTED_EMAIL.to_string();
+            state.3.user_email = REDACTED_EMAIL.to_string();
+            state.3.first_name = REDACTED.to_string();
+            encode_lufa_state(&state)
+        }
+        Err(_) => REDACTED.to_string(),
+    }
+}
+
+/// A [`Transport`] that forwards requests to another transport
+/// and records each request/response pair into a [`Cassette`].
+///
+/// Cookies and credentials are redacted before anything is
+/// recorded: request cookies are dropped, email and password
+/// form fields are masked, `Set-Cookie` values are masked and
+/// the email and name in `lufaState` are replaced. Response
+/// bodies are recorded as-is, so review a cassette before
+/// sharing it.
+///
+/// Interactions are kept in memory and written to the
+/// cassette file by [`RecordingTransport::save`], and once
+/// more when the transport is dropped, i.e. along with the
+/// last clone of the client recording through it. Failing
+/// to write the file never fails a request: call `save` to
+/// find out whether it was written.
+///
+/// # Examples
+///
+/// ```
+/// use lufa::{Lufa, Result};
+///
+/// async fn record() -> Result<()> {
+///   let client = Lufa::builder()
+///     .record("/tmp/lufa-cassette.json")
+///     .build()?;
+///
+///   client.auth().login("bob@example.com", "passw0rd").await?;
+///   client.orders().get_active().await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    path: PathBuf,
+    cassette: tokio::sync::Mutex<Cassette>,
+
+    // Whether interactions were recorded since the
+    // cassette was last saved
+    unsaved: AtomicBool,
+}
+
+impl RecordingTransport {
+    pub fn new<P: Into<PathBuf>>(inner: Arc<dyn Transport>, path: P) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            cassette: tokio::sync::Mutex::new(Cassette::default()),
+            unsaved: AtomicBool::new(false),
+        }
+    }
+
+    /// The interactions recorded so far.
+    pub async fn cassette(&self) -> Cassette {
+        self.cassette.lock().await.clone()
+    }
+
+    /// Writes the interactions recorded so far to the
+    /// cassette file, overwriting it.
+    pub async fn save(&self) -> Result<()> {
+        let cassette = self.cassette.lock().await;
+        self.unsaved.store(false, Ordering::SeqCst);
+
+        cassette.save(&self.path).await.inspect_err(|_| {
+            self.unsaved.store(true, Ordering::SeqCst);
+        })
+    }
+}
+
+impl Drop for RecordingTransport {
+    fn drop(&mut self) {
+        if !self.unsaved.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let result =
+            self.cassette.get_mut()._to_json().and_then(|bytes| {
+                std::fs::write(&self.path, bytes).map_err(|e| IoSnafu.into_error(e))
+            });
+
+        if let Err(_e) = result {
+            trace_event!(warn, error = %_e, "could not save the cassette");
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let response = self.inner.send(request.clone()).await?;
+
+        let mut cassette = self.cassette.lock().await;
+        cassette
+            .interactions
+            .push(Interaction::redacted(&request, &response));
+        self.unsaved.store(true, Ordering::SeqCst);
+
+        Ok(response)
+    }
+
+    fn cookies(&self, url: &Url) -> Option<String> {
+        self.inner.cookies(url)
+    }
+
+    fn add_cookie(&self, url: &Url, cookie: &str) {
+        self.inner.add_cookie(url, cookie)
+    }
+}
+
+/// A [`Transport`] that serves the responses recorded in a
+/// [`Cassette`] instead of sending requests over the network.
+///
+/// Each request is answered with the first recorded
+/// interaction that has the same method and path and has not
+/// been served yet. Request bodies are not compared. Requests
+/// without such an interaction fail with
+/// [`Error::CassetteMiss`](crate::Error::CassetteMiss).
+///
+/// # Examples
+///
+/// ```
+/// use lufa::{Lufa, Result};
+///
+/// async fn replay() -> Result<()> {
+///   let client = Lufa::builder()
+///     .replay("/tmp/lufa-cassette.json")
+///     .build()?;
+///
+///   client.auth().login("bob@example.com", "passw0rd").await?;
+///   client.orders().get_active().await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReplayTransport {
+    remaining: Mutex<VecDeque<Interaction>>,
+    jar: Jar,
+}
+
+impl ReplayTransport {
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            remaining: Mutex::new(cassette.interactions.into()),
+            jar: Jar::default(),
+        }
+    }
+
+    /// Loads the cassette to replay from a JSON file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Cassette::load(path).map(Self::new)
+    }
+
+    fn take(&self, request: &HttpRequest) -> Option<Interaction> {
+        let mut remaining = self.remaining.lock().unwrap();
+        let idx = remaining.iter().position(|i| i.matches(request))?;
+        remaining.remove(idx)
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let interaction = self.take(&request).ok_or_else(|| {
+            CassetteMissSnafu {
+                method: request.method.to_string(),
+                path: path_of(&request.url),
+            }
+            .build()
+        })?;
+
+        let recorded = interaction.response;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in recorded.headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+
+        let url = request
+            .url
+            .join(&recorded.path)
+            .unwrap_or(request.url.clone());
+
+        self.jar
+            .set_cookies(&mut headers.get_all(SET_COOKIE).iter(), &url);
+
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(recorded.status).unwrap_or(StatusCode::OK),
+            url,
+            headers,
+            body: recorded.body.into_bytes(),
+        })
+    }
+
+    fn cookies(&self, url: &Url) -> Option<String> {
+        self.jar
+            .cookies(url)
+            .and_then(|hv| hv.to_str().ok().map(|s| s.to_string()))
+    }
+
+    fn add_cookie(&self, url: &Url, cookie: &str) {
+        self.jar.add_cookie_str(cookie, url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use reqwest::Method;
+
+    use super::{redact_form, redact_set_cookie, Cassette, RecordingTransport, ReplayTransport};
+    use crate::{decode_lufa_state, Error, Lufa, MemoryResponse, MemoryTransport};
+
+    #[test]
+    fn test_redact_form() {
+        let body = b"LoginForm%5Buser_email%5D=bob%40example.com&LoginForm%5Bpassword%5D=passw0rd&user_id=123456";
+        assert_eq!(
+            redact_form(body),
+            "LoginForm%5Buser_email%5D=REDACTED&LoginForm%5Bpassword%5D=REDACTED&user_id=123456"
+        );
+    }
+
+    #[test]
+    fn test_redact_set_cookie() {
+        assert_eq!(
+            redact_set_cookie("PHPSESSID=abc123; path=/; HttpOnly"),
+            "PHPSESSID=REDACTED; path=/; HttpOnly"
+        );
+        assert_eq!(
+            redact_set_cookie("lufaState=deleted; Max-Age=0"),
+            "lufaState=deleted; Max-Age=0"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let path = std::env::temp_dir().join(format!("lufa-cassette-{}.json", std::process::id()));
+
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::logged_in("123456", "bob@example.com"),
+        );
+        transport.respond(Method::GET, "/logout", MemoryResponse::ok().body("bye"));
+
+        let recorder = Arc::new(RecordingTransport::new(Arc::new(transport), &path));
+        let client = Lufa::builder().transport(recorder.clone()).build().unwrap();
+        client
+            .auth()
+            .login("bob@example.com", "passw0rd")
+            .await
+            .unwrap();
+        client.auth().logout().await.unwrap();
+        recorder.save().await.unwrap();
+
+        // Nothing identifying is left in the cassette
+        let cassette = Cassette::load(&path).unwrap();
+        assert_eq!(cassette, recorder.cassette().await);
+        assert_eq!(cassette.interactions.len(), 2);
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert!(!json.contains("passw0rd"));
+        assert!(!json.contains("bob"));
+        assert!(!json.contains("abc123"));
+
+        let state = cassette.interactions[0]
+            .response
+            .headers
+            .iter()
+            .find_map(|(_, v)| v.strip_prefix("lufaState="))
+            .unwrap();
+        let state = decode_lufa_state(state.split(';').next().unwrap()).unwrap();
+        assert_eq!(state.0, "123456");
+        assert_eq!(state.1, "redacted@example.com");
+
+        // The replayed session logs in as the redacted user
+        let client = Lufa::builder()
+            .transport(ReplayTransport::open(&path).unwrap())
+            .build()
+            .unwrap();
+        client
+            .auth()
+            .login("bob@example.com", "passw0rd")
+            .await
+            .unwrap();
+        assert_eq!(client.user_id("/").await.unwrap(), "123456");
+        client.auth().logout().await.unwrap();
+
+        let res = client.auth().logout().await;
+        assert!(matches!(res, Err(Error::CassetteMiss { .. })));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_on_drop() {
+        let path = std::env::temp_dir().join(format!("lufa-dropped-{}.json", std::process::id()));
+
+        let transport = MemoryTransport::new();
+        transport.respond(Method::GET, "/logout", MemoryResponse::ok());
+
+        let recorder = RecordingTransport::new(Arc::new(transport), &path);
+        let client = Lufa::builder().transport(recorder).build().unwrap();
+        client.auth().logout().await.unwrap();
+        client.auth().logout().await.unwrap();
+        assert!(!path.exists());
+
+        drop(client);
+        assert_eq!(Cassette::load(&path).unwrap().interactions.len(), 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_failure() {
+        let path = std::env::temp_dir()
+            .join("lufa-missing-dir")
+            .join("cassette.json");
+
+        let transport = MemoryTransport::new();
+        transport.respond(Method::GET, "/logout", MemoryResponse::ok());
+
+        // The request succeeds even though the cassette
+        // cannot be written, which only `save` reports
+        let recorder = Arc::new(RecordingTransport::new(Arc::new(transport), &path));
+        let client = Lufa::builder().transport(recorder.clone()).build().unwrap();
+        client.auth().logout().await.unwrap();
+
+        assert!(matches!(recorder.save().await, Err(Error::Io { .. })));
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{
+    cookie::{CookieStore, Jar},
+    Url,
+};
+use snafu::IntoError;
+
+use crate::error::{ReqwestSnafu, Result};
+
+use super::{HttpRequest, HttpResponse, Transport};
+
+/// The default [`Transport`], backed by a `reqwest` client
+/// and its cookie jar.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    jar: Arc<Jar>,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport with a default `reqwest` client.
+    pub fn new() -> Result<Self> {
+        Self::from_builder(reqwest::Client::builder())
+    }
+
+    /// Creates a transport from a `reqwest` client builder.
+    ///
+    /// The builder's cookie store is replaced with the
+    /// transport's own jar.
+    pub fn from_builder(builder: reqwest::ClientBuilder) -> Result<Self> {
+        let jar = Arc::new(Jar::default());
+        let client = builder
+            .cookie_provider(jar.clone())
+            .build()
+            .map_err(|e| ReqwestSnafu.into_error(e))?;
+
+        Ok(Self { client, jar })
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, request.url)
+            .headers(request.headers);
+
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| ReqwestSnafu.into_error(e))?;
+
+        let status = response.status();
+        let url = response.url().clone();
+        let headers = response.headers().clone();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ReqwestSnafu.into_error(e))?
+            .to_vec();
+
+        Ok(HttpResponse {
+            status,
+            url,
+            headers,
+            body,
+        })
+    }
+
+    fn cookies(&self, url: &Url) -> Option<String> {
+        self.jar
+            .cookies(url)
+            .and_then(|hv| hv.to_str().ok().map(|s| s.to_string()))
+    }
+
+    fn add_cookie(&self, url: &Url, cookie: &str) {
+        self.jar.add_cookie_str(cookie, url);
+    }
+}
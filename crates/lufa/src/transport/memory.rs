@@ -0,0 +1,296 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use reqwest::{
+    cookie::{CookieStore, Jar},
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, COOKIE, SET_COOKIE},
+    Method, StatusCode, Url,
+};
+use serde::Serialize;
+
+use crate::error::Result;
+
+use super::{HttpRequest, HttpResponse, Transport};
+
+/// A scripted response served by a [`MemoryTransport`].
+#[derive(Debug, Clone)]
+pub struct MemoryResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    redirect: Option<String>,
+}
+
+impl MemoryResponse {
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: Vec::new(),
+            redirect: None,
+        }
+    }
+
+    /// An empty `200 OK` response.
+    pub fn ok() -> Self {
+        Self::new(StatusCode::OK)
+    }
+
+    /// Sets a JSON body.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.body = serde_json::to_vec(body).unwrap_or_default();
+        self
+    }
+
+    /// Sets a raw body.
+    pub fn body<B: Into<Vec<u8>>>(mut self, body: B) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Adds a `Set-Cookie` header, e.g. `"PHPSESSID=abc; Path=/"`.
+    pub fn set_cookie(mut self, cookie: &str) -> Self {
+        if let Ok(value) = HeaderValue::from_str(cookie) {
+            self.headers.append(SET_COOKIE, value);
+        }
+        self
+    }
+
+    /// Makes the response appear as if the request had been
+    /// redirected to the given path, e.g. `/login`.
+    pub fn redirect_to<P: AsRef<str>>(mut self, path: P) -> Self {
+        self.redirect = Some(path.as_ref().to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+impl MemoryResponse {
+    // A successful login response, with a `lufaState`
+    // cookie encoded the way the server does it
+    pub(crate) fn logged_in(user_id: &str, email: &str) -> Self {
+        let state = crate::models::cookies::LufaState(
+            user_id.to_string(),
+            email.to_string(),
+            0,
+            crate::models::cookies::LufaStateInfo {
+                user_email: email.to_string(),
+                first_name: "Bob".to_string(),
+            },
+        );
+
+        Self::ok()
+            .set_cookie("PHPSESSID=abc123; Path=/")
+            .set_cookie(&format!(
+                "lufaState={}; Path=/",
+                crate::encode_lufa_state(&state)
+            ))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Script {
+    routes: HashMap<(Method, String), VecDeque<MemoryResponse>>,
+    requests: Vec<HttpRequest>,
+}
+
+/// An in-memory [`Transport`] that serves scripted responses
+/// and records every request it receives, for use in tests.
+///
+/// Routes are given without the language prefix, e.g.
+/// `/users/profileData` matches both `/en/users/profileData`
+/// and `/fr/users/profileData`. Requests to unscripted routes
+/// are answered with `404 Not Found`.
+///
+/// # Examples
+///
+/// ```
+/// use lufa::{Lufa, MemoryResponse, MemoryTransport, Result};
+/// use reqwest::Method;
+///
+/// async fn scripted() -> Result<()> {
+///   let transport = MemoryTransport::new();
+///   transport.respond(Method::GET, "/logout", MemoryResponse::ok());
+///
+///   let client = Lufa::builder().transport(transport.clone()).build()?;
+///   client.auth().logout().await?;
+///
+///   assert_eq!(transport.requests().len(), 1);
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTransport {
+    script: Arc<Mutex<Script>>,
+    jar: Arc<Jar>,
+}
+
+impl MemoryTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scripts a response for the given route.
+    ///
+    /// Responses scripted for the same route are served in
+    /// order, and the last one keeps being served once all
+    /// others have been.
+    pub fn respond<P: AsRef<str>>(&self, method: Method, path: P, response: MemoryResponse) {
+        let mut script = self.script.lock().unwrap();
+        script
+            .routes
+            .entry((method, path.as_ref().to_string()))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// The requests received so far, with the cookies
+    /// that were attached to them.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.script.lock().unwrap().requests.clone()
+    }
+
+    // Strips the language prefix from a URL path
+    fn route(url: &Url) -> String {
+        let path = url.path();
+
+        match path.get(..4) {
+            Some("/en/") | Some("/fr/") => path[3..].to_string(),
+            _ => path.to_string(),
+        }
+    }
+
+    fn next_response(&self, request: &HttpRequest) -> Option<MemoryResponse> {
+        let mut script = self.script.lock().unwrap();
+        script.requests.push(request.clone());
+
+        let key = (request.method.clone(), Self::route(&request.url));
+        let responses = script.routes.get_mut(&key)?;
+
+        match responses.len() {
+            0 => None,
+            1 => responses.front().cloned(),
+            _ => responses.pop_front(),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn send(&self, mut request: HttpRequest) -> Result<HttpResponse> {
+        if let Some(cookies) = self.cookies(&request.url) {
+            if let Ok(value) = HeaderValue::from_str(&cookies) {
+                request.headers.insert(COOKIE, value);
+            }
+        }
+
+        let response = self
+            .next_response(&request)
+            .unwrap_or(MemoryResponse::new(StatusCode::NOT_FOUND));
+
+        let mut url = request.url.clone();
+        if let Some(redirect) = response.redirect.as_ref() {
+            // Keep the language prefix, if any
+            let path = request.url.path();
+            let prefix = &path[..path.len() - Self::route(&request.url).len()];
+            url.set_path(&format!("{}{}", prefix, redirect));
+        }
+
+        self.jar
+            .set_cookies(&mut response.headers.get_all(SET_COOKIE).iter(), &url);
+
+        Ok(HttpResponse {
+            status: response.status,
+            url,
+            headers: response.headers,
+            body: response.body,
+        })
+    }
+
+    fn cookies(&self, url: &Url) -> Option<String> {
+        self.jar
+            .cookies(url)
+            .and_then(|hv| hv.to_str().ok().map(|s| s.to_string()))
+    }
+
+    fn add_cookie(&self, url: &Url, cookie: &str) {
+        self.jar.add_cookie_str(cookie, url);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{header::COOKIE, Method, StatusCode, Url};
+
+    use super::{MemoryResponse, MemoryTransport};
+    use crate::transport::{HttpRequest, Transport};
+
+    fn request(method: Method, url: &str) -> HttpRequest {
+        HttpRequest::new(method, Url::parse(url).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_scripted_responses() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::GET,
+            "/profile",
+            MemoryResponse::new(StatusCode::BAD_GATEWAY),
+        );
+        transport.respond(Method::GET, "/profile", MemoryResponse::ok().body("ok"));
+
+        let url = "http://lufa.test/en/profile";
+        let res = transport.send(request(Method::GET, url)).await.unwrap();
+        assert_eq!(res.status, StatusCode::BAD_GATEWAY);
+
+        for _ in 0..2 {
+            let res = transport.send(request(Method::GET, url)).await.unwrap();
+            assert_eq!(res.status, StatusCode::OK);
+            assert_eq!(res.text(), "ok");
+        }
+
+        let res = transport.send(request(Method::POST, url)).await.unwrap();
+        assert_eq!(res.status, StatusCode::NOT_FOUND);
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_cookies_and_redirects() {
+        let transport = MemoryTransport::new();
+        transport.respond(
+            Method::POST,
+            "/login",
+            MemoryResponse::ok().set_cookie("PHPSESSID=abc; Path=/"),
+        );
+        transport.respond(
+            Method::GET,
+            "/profile",
+            MemoryResponse::ok().redirect_to("/login"),
+        );
+
+        transport
+            .send(request(Method::POST, "http://lufa.test/fr/login"))
+            .await
+            .unwrap();
+
+        let res = transport
+            .send(request(Method::GET, "http://lufa.test/fr/profile"))
+            .await
+            .unwrap();
+        assert_eq!(res.url.path(), "/fr/login");
+
+        let requests = transport.requests();
+        assert_eq!(requests[1].headers.get(COOKIE).unwrap(), "PHPSESSID=abc");
+    }
+}
@@ -0,0 +1,143 @@
+mod memory;
+pub use memory::*;
+
+mod client;
+pub use client::*;
+
+mod cassette;
+pub use cassette::*;
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE, SET_COOKIE},
+    Method, StatusCode, Url,
+};
+use serde::Serialize;
+use snafu::IntoError;
+
+use crate::error::{FormEncodingSnafu, Result, SerdeJsonSnafu};
+
+/// The means by which a [`Lufa`](crate::Lufa) client talks HTTP.
+///
+/// A transport sends requests, returns the final response once
+/// any redirects have been followed, and keeps the cookies that
+/// the server sets along the way.
+#[async_trait]
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends a request, attaching any stored cookies that
+    /// apply to its URL.
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+
+    /// Returns the `Cookie` header value for the given URL,
+    /// e.g. `"PHPSESSID=abc; lufaState=def"`.
+    fn cookies(&self, url: &Url) -> Option<String>;
+
+    /// Stores a cookie for the given URL, as if it had been
+    /// received in a `Set-Cookie` header.
+    fn add_cookie(&self, url: &Url, cookie: &str);
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).send(request).await
+    }
+
+    fn cookies(&self, url: &Url) -> Option<String> {
+        (**self).cookies(url)
+    }
+
+    fn add_cookie(&self, url: &Url, cookie: &str) {
+        (**self).add_cookie(url, cookie)
+    }
+}
+
+/// An HTTP request issued through a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+
+    // Whether the request can safely be sent more than once,
+    // which is the case for all `GET`s and for the `POST`s
+    // that only read data
+    pub idempotent: bool,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: Url) -> Self {
+        Self {
+            idempotent: method == Method::GET,
+            method,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// Marks the request as safe to send more than once.
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Sets a raw request body.
+    pub fn body<B: Into<Vec<u8>>>(mut self, body: B) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Sets a JSON request body.
+    pub fn json<T: Serialize + ?Sized>(mut self, payload: &T) -> Result<Self> {
+        let body = serde_json::to_vec(payload).map_err(|e| SerdeJsonSnafu.into_error(e))?;
+
+        self.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        self.body = Some(body);
+        Ok(self)
+    }
+
+    /// Sets a URL-encoded form request body.
+    pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Result<Self> {
+        let body =
+            serde_urlencoded::to_string(form).map_err(|e| FormEncodingSnafu.into_error(e))?;
+
+        self.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        self.body = Some(body.into_bytes());
+        Ok(self)
+    }
+}
+
+/// An HTTP response received through a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+
+    // The URL of the final response, after
+    // following any redirects
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// The response body as (lossily decoded) text.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// The raw values of all `Set-Cookie` headers.
+    pub fn set_cookies(&self) -> impl Iterator<Item = &str> {
+        self.headers
+            .get_all(SET_COOKIE)
+            .into_iter()
+            .filter_map(|v| v.to_str().ok())
+    }
+}
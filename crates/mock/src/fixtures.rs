@@ -0,0 +1,9 @@
+// The payloads served by the mock server, taken
+// from responses of the real Lufa API. They are the
+// same fixtures the `lufa-models` tests parse, so
+// that there is only one copy of each to update.
+
+pub(crate) const PROFILE: &str = include_str!("../../models/tests/fixtures/profile.json");
+pub(crate) const BILLING: &str = include_str!("../../models/tests/fixtures/billing.json");
+pub(crate) const ORDER: &str = include_str!("../../models/tests/fixtures/order.json");
+pub(crate) const TRACKING: &str = include_str!("../../models/tests/fixtures/tracking.json");
@@ -0,0 +1,20 @@
+mod fixtures;
+mod routes;
+mod server;
+
+pub use server::*;
+
+/// The email of the only account known to the mock server.
+pub const EMAIL: &str = "john@example.com";
+
+/// The password of the only account known to the mock server.
+pub const PASSWORD: &str = "passw0rd";
+
+/// The ID of the only account known to the mock server.
+pub const USER_ID: &str = "123456";
+
+/// The first name of the only account known to the mock server.
+pub const FIRST_NAME: &str = "John";
+
+/// The ID of the account's active order.
+pub const ORDER_ID: &str = "12345678";
@@ -0,0 +1,207 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Form, Path, State},
+    http::{
+        header::{CONTENT_TYPE, COOKIE, SET_COOKIE},
+        HeaderMap,
+    },
+    response::{AppendHeaders, Html, IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Router,
+};
+use serde_json::json;
+
+use crate::{fixtures, server::Sessions, EMAIL, FIRST_NAME, ORDER_ID, PASSWORD, USER_ID};
+
+const LOGIN_PAGE: &str = "<html><body><form id=\"login-form\"></form></body></html>";
+
+// The cookie the server sends to clear `lufaState`
+const DELETED_STATE: &str =
+    "lufaState=deleted; expires=Thu, 01-Jan-1970 00:00:01 GMT; Max-Age=0; path=/";
+
+type SharedSessions = State<Arc<Sessions>>;
+
+pub(crate) fn router(sessions: Arc<Sessions>) -> Router {
+    Router::new()
+        .route("/:lang/login", get(login_page).post(login))
+        .route("/:lang/logout", get(logout))
+        .route("/:lang/users/profileData", post(profile_data))
+        .route("/:lang/users/billingData", post(billing_data))
+        .route(
+            "/:lang/superMarket/GetUserOrderDetails",
+            get(user_order_details),
+        )
+        .route("/:lang/orders/getTrackOrderData", post(track_order_data))
+        .with_state(sessions)
+}
+
+async fn login_page() -> Html<&'static str> {
+    Html(LOGIN_PAGE)
+}
+
+async fn login(
+    State(sessions): SharedSessions,
+    Form(form): Form<HashMap<String, String>>,
+) -> Response {
+    let email = form.get("LoginForm[user_email]").map(String::as_str);
+    let password = form.get("LoginForm[password]").map(String::as_str);
+
+    // The login page is served again, without
+    // a session, when the credentials are wrong
+    if email != Some(EMAIL) || password != Some(PASSWORD) {
+        return (
+            AppendHeaders([(SET_COOKIE, DELETED_STATE.to_string())]),
+            Html(LOGIN_PAGE),
+        )
+            .into_response();
+    }
+
+    let session = sessions.open();
+
+    (
+        AppendHeaders([
+            (
+                SET_COOKIE,
+                format!("PHPSESSID={}; path=/; HttpOnly", session),
+            ),
+            (
+                SET_COOKIE,
+                format!("lufaState={}; Max-Age=2592000; path=/", lufa_state()),
+            ),
+        ]),
+        Html("<html><body>Welcome back!</body></html>"),
+    )
+        .into_response()
+}
+
+async fn logout(State(sessions): SharedSessions, headers: HeaderMap) -> Response {
+    if let Some(session) = session_id(&headers) {
+        sessions.close(&session);
+    }
+
+    (
+        AppendHeaders([(SET_COOKIE, DELETED_STATE.to_string())]),
+        Html(LOGIN_PAGE),
+    )
+        .into_response()
+}
+
+async fn profile_data(
+    State(sessions): SharedSessions,
+    Path(lang): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> Response {
+    if !is_logged_in(&sessions, &headers) {
+        return to_login(&lang);
+    }
+
+    match form.get("user_id").map(String::as_str) {
+        Some(USER_ID) => api_success(fixtures::PROFILE),
+        _ => api_failure("Invalid user"),
+    }
+}
+
+async fn billing_data(
+    State(sessions): SharedSessions,
+    Path(lang): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> Response {
+    if !is_logged_in(&sessions, &headers) {
+        return to_login(&lang);
+    }
+
+    match form.get("user_id").map(String::as_str) {
+        Some(USER_ID) => api_success(fixtures::BILLING),
+        _ => api_failure("Invalid user"),
+    }
+}
+
+async fn user_order_details(
+    State(sessions): SharedSessions,
+    Path(lang): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if !is_logged_in(&sessions, &headers) {
+        return to_login(&lang);
+    }
+
+    json_response(fixtures::ORDER.to_string())
+}
+
+async fn track_order_data(
+    State(sessions): SharedSessions,
+    Path(lang): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> Response {
+    if !is_logged_in(&sessions, &headers) {
+        return to_login(&lang);
+    }
+
+    match form.get("order_id").map(String::as_str) {
+        Some(ORDER_ID) => json_response(fixtures::TRACKING.to_string()),
+        _ => api_failure("Order not found"),
+    }
+}
+
+// The `lufaState` cookie value: a 40-character signature
+// followed by the urlencoded, PHP-serialized user state
+fn lufa_state() -> String {
+    let state = format!(
+        "a:4:{{i:0;s:{}:\"{}\";i:1;s:{}:\"{}\";i:2;i:0;i:3;a:2:{{s:10:\"user_email\";s:{}:\"{}\";s:10:\"first_name\";s:{}:\"{}\";}}}}",
+        USER_ID.len(),
+        USER_ID,
+        EMAIL.len(),
+        EMAIL,
+        EMAIL.len(),
+        EMAIL,
+        FIRST_NAME.len(),
+        FIRST_NAME,
+    );
+
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+
+    format!("{:040x}{}", hasher.finish(), urlencoding::encode(&state))
+}
+
+fn session_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|(name, _)| *name == "PHPSESSID")
+        .map(|(_, value)| value.to_string())
+}
+
+fn is_logged_in(sessions: &Sessions, headers: &HeaderMap) -> bool {
+    session_id(headers).is_some_and(|s| sessions.is_active(&s))
+}
+
+// Requests without a valid session are
+// redirected to the login page
+fn to_login(lang: &str) -> Response {
+    Redirect::to(&format!("/{}/login", lang)).into_response()
+}
+
+fn json_response(body: String) -> Response {
+    ([(CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+fn api_success(payload: &str) -> Response {
+    let data: serde_json::Value = serde_json::from_str(payload).unwrap_or_default();
+    json_response(json!({ "success": true, "data": data, "message": null }).to_string())
+}
+
+fn api_failure(message: &str) -> Response {
+    json_response(json!({ "success": false, "data": [], "message": message }).to_string())
+}
@@ -0,0 +1,115 @@
+use std::{
+    collections::HashSet,
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::routes;
+
+// The sessions the server has handed out, by `PHPSESSID`
+#[derive(Debug, Default)]
+pub(crate) struct Sessions {
+    next_id: AtomicU64,
+    active: Mutex<HashSet<String>>,
+}
+
+impl Sessions {
+    pub(crate) fn open(&self) -> String {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let session = format!("mock{:028x}", id);
+
+        self.active.lock().unwrap().insert(session.clone());
+        session
+    }
+
+    pub(crate) fn close(&self, session: &str) {
+        self.active.lock().unwrap().remove(session);
+    }
+
+    pub(crate) fn is_active(&self, session: &str) -> bool {
+        self.active.lock().unwrap().contains(session)
+    }
+
+    fn expire_all(&self) {
+        self.active.lock().unwrap().clear();
+    }
+
+    fn count(&self) -> usize {
+        self.active.lock().unwrap().len()
+    }
+}
+
+/// An in-process HTTP server that mimics the parts of the
+/// Lufa API used by the `lufa` client, serving fixed data
+/// for a single account (see [`EMAIL`](crate::EMAIL) and
+/// [`PASSWORD`](crate::PASSWORD)).
+///
+/// The server listens on a random local port and shuts
+/// down when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use lufa_mock::MockServer;
+///
+/// async fn start() -> std::io::Result<()> {
+///   let server = MockServer::start().await?;
+///   println!("Listening on {}", server.url());
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    sessions: Arc<Sessions>,
+    handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts the server on a random local port.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let sessions = Arc::new(Sessions::default());
+        let router = routes::router(sessions.clone());
+
+        let handle = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Ok(Self {
+            addr,
+            sessions,
+            handle,
+        })
+    }
+
+    /// The base URL of the server, e.g. `http://127.0.0.1:41234`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Expires every session, so that subsequent requests are
+    /// redirected to the login page as if they had timed out.
+    pub fn expire_sessions(&self) {
+        self.sessions.expire_all();
+    }
+
+    /// The number of sessions that are currently logged in.
+    pub fn active_sessions(&self) -> usize {
+        self.sessions.count()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
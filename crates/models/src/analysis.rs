@@ -0,0 +1,330 @@
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use rusty_money::{iso, Money};
+
+use crate::{
+    billing::{Transaction, TransactionType},
+    orders::{Order, OrderItem},
+    ProductId,
+};
+
+// A WeightAdjustment compares what a product of an order was
+// estimated to cost with what it cost once packed, as adjusted
+// by the "Product price adjustments based on weight" entries
+// of the order's transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightAdjustment {
+    // The name of the product, as given by the adjustments
+    pub product_name: String,
+
+    // The product ID of the matching order item, if any
+    pub product_id: Option<ProductId>,
+
+    // The vendor of the matching order item, if any
+    pub vendor: Option<String>,
+
+    // The number of adjustment entries for the product
+    pub entries: usize,
+
+    // The extra amount charged for the product once packed.
+    // Positive when more was packed than listed, negative
+    // when less was.
+    pub price_delta: Money<'static, iso::Currency>,
+
+    // The listed price of the product times its quantity
+    pub estimated_cost: Option<Money<'static, iso::Currency>>,
+
+    // The listed weight of the product times its quantity
+    pub estimated_weight: Option<f64>,
+
+    // The weight packed on top of the listed weight, as
+    // derived from the price per unit of the product
+    pub weight_delta: Option<f64>,
+
+    // What the weights are measured in, e.g. "g"
+    pub weight_unit: Option<String>,
+}
+
+impl WeightAdjustment {
+    // What the product cost once packed
+    pub fn actual_cost(&self) -> Option<Money<'static, iso::Currency>> {
+        self.estimated_cost.as_ref().map(|cost| {
+            Money::from_decimal(cost.amount() + self.price_delta.amount(), cost.currency())
+        })
+    }
+
+    // The weight of the product once packed
+    pub fn actual_weight(&self) -> Option<f64> {
+        Some(self.estimated_weight? + self.weight_delta?)
+    }
+
+    // Whether more was packed than listed
+    pub fn is_over_packed(&self) -> bool {
+        self.price_delta.is_positive()
+    }
+
+    // Whether less was packed than listed
+    pub fn is_under_packed(&self) -> bool {
+        self.price_delta.is_negative()
+    }
+}
+
+// Joins the items of an order with the weight adjustments of
+// its transactions, returning one report per adjusted product
+// in the order the adjustments were made. Adjustments are
+// matched to items by product name, ignoring case and
+// surrounding whitespace.
+pub fn weight_adjustments(order: &Order, transactions: &[Transaction]) -> Vec<WeightAdjustment> {
+    let mut entries: Vec<_> = transactions
+        .iter()
+        .flat_map(|tx| tx.entries.iter())
+        .filter(|entry| entry.order_id == order.id)
+        .filter(|entry| entry._type == TransactionType::WeightAdjustment)
+        .filter_map(|entry| Some((entry.product_name.as_deref()?, entry)))
+        .collect();
+    entries.sort_by_key(|(_, entry)| entry.timestamp);
+
+    let mut adjustments: Vec<WeightAdjustment> = Vec::new();
+
+    for (name, entry) in entries {
+        // Entries are charges when negative, so a negative
+        // amount means more was charged than estimated
+        let charged = -*entry.amount.amount();
+
+        match adjustments
+            .iter_mut()
+            .find(|adjustment| same_name(&adjustment.product_name, name))
+        {
+            Some(adjustment) => {
+                adjustment.entries += 1;
+                let currency = adjustment.price_delta.currency();
+                adjustment.price_delta =
+                    Money::from_decimal(adjustment.price_delta.amount() + charged, currency);
+            }
+            None => adjustments.push(WeightAdjustment {
+                product_name: name.trim().to_string(),
+                product_id: None,
+                vendor: None,
+                entries: 1,
+                price_delta: Money::from_decimal(charged, entry.amount.currency()),
+                estimated_cost: None,
+                estimated_weight: None,
+                weight_delta: None,
+                weight_unit: None,
+            }),
+        }
+    }
+
+    for adjustment in adjustments.iter_mut() {
+        if let Some(item) = order
+            .items
+            .iter()
+            .find(|item| same_name(&item.name, &adjustment.product_name))
+        {
+            estimate(adjustment, item);
+        }
+    }
+
+    adjustments
+}
+
+// Fills in the estimates of an adjustment from its order item
+fn estimate(adjustment: &mut WeightAdjustment, item: &OrderItem) {
+    let quantity = Decimal::from(item.quantity);
+
+    adjustment.product_id = Some(item.product_id.clone());
+    adjustment.vendor = Some(item.vendor.clone());
+    adjustment.estimated_cost = Some(Money::from_decimal(
+        item.defined_price.amount() * quantity,
+        item.defined_price.currency(),
+    ));
+    adjustment.estimated_weight = item.weight.map(|weight| weight * item.quantity as f64);
+    adjustment.weight_unit = item.weight_unit.clone();
+
+    // The price per unit is only comparable when given
+    // per the same unit as the listed weight
+    if item.ppu_unit.is_some() && item.ppu_unit == item.weight_unit {
+        let price = item.ppu_price.as_ref().and_then(|p| p.amount().to_f64());
+        let delta = adjustment.price_delta.amount().to_f64();
+
+        adjustment.weight_delta = match (price, item.ppu_quantity, delta) {
+            (Some(price), Some(quantity), Some(delta)) if price > 0.0 => {
+                Some(delta / price * quantity)
+            }
+            _ => None,
+        };
+    }
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use rusty_money::{iso, Money};
+
+    use super::weight_adjustments;
+    use crate::{billing::Transaction, orders::Order};
+
+    fn order() -> Order {
+        serde_json::from_str(
+            r#"
+          {
+            "orderId": "12345678",
+            "orderStatus": "4",
+            "orderDate": "2024-05-12",
+            "orderDetails": [
+              {
+                "product_id": "15304",
+                "description": null,
+                "on_sale": null,
+                "default_price": "0.00",
+                "defined_price": "4.20",
+                "price": "0.00",
+                "paid_price": "0.00",
+                "avg_p_p": "2.10",
+                "avg_p_q": "100",
+                "avg_p_u": "g",
+                "quantity_in_basket": "2",
+                "p_name": "Champignons shiitakes",
+                "s_name": "Lufa Farms Ville Saint-Laurent",
+                "image_url": "",
+                "cat_na": "Vegetables",
+                "weight": "200",
+                "units": "sac",
+                "unit": "g",
+                "image_urls": {}
+              }
+            ],
+            "orderRecipes": [],
+            "checkoutAmounts": {
+              "total": "8.40",
+              "subtotal": "8.40",
+              "delivery_fees": "0.00",
+              "remaining_balance": "0.00",
+              "balance": "0.00",
+              "consigne_amount": "0.00",
+              "national_tax": "0.00",
+              "provincial_tax": "0.00",
+              "coupon_discount_amount": "0.00",
+              "order_donation": "0.00",
+              "donation_discount": "0.00",
+              "available_weekly": "0.00",
+              "remaining_weekly": "0.00"
+            }
+          }
+        "#,
+        )
+        .unwrap()
+    }
+
+    fn transaction(amount: &str, time: &str, product_name: &str) -> Transaction {
+        serde_json::from_str(&format!(
+            r#"
+          {{
+            "transactions": {{
+              "38": {{
+                "transaction_id": "12345678",
+                "transaction_order_id": "12345678",
+                "transaction_type": 38,
+                "transaction_amount": {},
+                "transaction_time": "{}",
+                "transaction_last_4": null,
+                "product_name": "{}",
+                "transaction_type_formatted": "Product price adjustments based on weight"
+              }}
+            }},
+            "order_id": "12345678",
+            "total": "-8.40",
+            "title_string": "Basket Order - Credit card payment 1234",
+            "transaction_time": "2024-05-07 00:32:54",
+            "transaction_type": "21"
+          }}
+        "#,
+            amount, time, product_name
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_weight_adjustments() {
+        let transactions = vec![
+            transaction("-1.05", "2024-05-07 14:38:31", "Champignons shiitakes"),
+            transaction("0.21", "2024-05-07 14:40:00", "champignons shiitakes "),
+            transaction("0.50", "2024-05-07 14:39:00", "Green Kale"),
+        ];
+
+        let adjustments = weight_adjustments(&order(), &transactions);
+        assert_eq!(adjustments.len(), 2);
+
+        let shiitakes = &adjustments[0];
+        assert_eq!(shiitakes.product_name, "Champignons shiitakes");
+        assert_eq!(shiitakes.entries, 2);
+        assert_eq!(shiitakes.product_id.as_ref().unwrap(), "15304");
+        assert_eq!(shiitakes.price_delta, Money::from_minor(84, iso::CAD));
+        assert_eq!(
+            shiitakes.estimated_cost,
+            Some(Money::from_minor(840, iso::CAD))
+        );
+        assert_eq!(
+            shiitakes.actual_cost(),
+            Some(Money::from_minor(924, iso::CAD))
+        );
+        assert_eq!(shiitakes.estimated_weight, Some(400.0));
+        assert_eq!(shiitakes.actual_weight().map(f64::round), Some(440.0));
+        assert!(shiitakes.is_over_packed());
+
+        // Adjustments of products that are not in the order
+        // are still reported, without estimates
+        let kale = &adjustments[1];
+        assert_eq!(kale.product_name, "Green Kale");
+        assert_eq!(kale.product_id, None);
+        assert_eq!(kale.actual_cost(), None);
+        assert!(kale.is_under_packed());
+    }
+
+    #[test]
+    fn test_mixed_entries() {
+        // A payment entry of the same order and product is not
+        // an adjustment, and must not be counted as one
+        let transaction: Transaction = serde_json::from_str(
+            r#"
+          {
+            "transactions": {
+              "20": {
+                "transaction_id": "12345678",
+                "transaction_order_id": "12345678",
+                "transaction_type": "20",
+                "transaction_amount": "-4.18",
+                "transaction_time": "2024-05-07 00:32:54",
+                "transaction_last_4": null,
+                "product_name": "Champignons shiitakes",
+                "transaction_type_formatted": "Basket Order - Remaining credits payment"
+              },
+              "38": {
+                "transaction_id": "12345678",
+                "transaction_order_id": "12345678",
+                "transaction_type": 38,
+                "transaction_amount": -1.05,
+                "transaction_time": "2024-05-07 14:38:31",
+                "transaction_last_4": null,
+                "product_name": "Champignons shiitakes",
+                "transaction_type_formatted": "Product price adjustments based on weight"
+              }
+            },
+            "order_id": "12345678",
+            "total": "-136.73",
+            "title_string": "Basket Order - Credit card payment 1234",
+            "transaction_time": "2024-05-07 00:32:54",
+            "transaction_type": "21"
+          }
+        "#,
+        )
+        .unwrap();
+
+        let adjustments = weight_adjustments(&order(), &[transaction]);
+        assert_eq!(adjustments.len(), 1);
+        assert_eq!(adjustments[0].entries, 1);
+        assert_eq!(adjustments[0].price_delta, Money::from_minor(105, iso::CAD));
+    }
+}
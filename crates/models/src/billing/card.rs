@@ -1,16 +1,19 @@
-use crate::utils;
+use crate::{utils, CardId};
 use chrono::NaiveDate;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
+
 // A Card represents a user's saved card
 // information from the platform
 #[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Card {
     // The card ID
     #[serde(rename = "cc_id")]
-    pub id: String,
+    pub id: CardId,
 
     // The brand of the card, e.g. "Visa", "MasterCard"
     #[serde(rename = "cc_type")]
@@ -21,7 +24,11 @@ pub struct Card {
     pub last_four: String,
 
     // The expiry date of the card
-    #[serde(rename = "cc_exp", deserialize_with = "utils::naive_date_from_str")]
+    #[serde(
+        rename = "cc_exp",
+        deserialize_with = "utils::naive_date_from_str",
+        serialize_with = "utils::naive_date_to_str"
+    )]
     pub expiry: NaiveDate,
 
     // Whether or not the card is expired
@@ -37,6 +44,26 @@ pub struct Card {
     // The type of card, e.g. "primary", "secondary"
     #[serde(rename = "type")]
     pub _type: String,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for Card {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "Card",
+        fields: &[
+            Field::required("cc_id", drift::STRING),
+            Field::required("cc_type", drift::STRING),
+            Field::required("cc_last_4", drift::STRING),
+            Field::required("cc_exp", drift::STRING),
+            Field::required("expired", drift::BOOL),
+            Field::required("cc_priority", drift::NUMERIC),
+            Field::required("type", drift::STRING),
+        ],
+    };
 }
 
 #[cfg(test)]
@@ -46,6 +73,7 @@ mod tests {
     use super::Card;
 
     #[test]
+    #[allow(clippy::zero_prefixed_literal, clippy::bool_assert_comparison)]
     fn test_deserialize() {
         let s = r#"
           {
@@ -64,6 +92,8 @@ mod tests {
         let card: Result<Card, _> = serde_json::from_str(s);
         assert!(card.is_ok());
 
+        crate::se::assert_roundtrip(card.as_ref().unwrap());
+
         let card = card.unwrap();
 
         assert_eq!(card.id, "123456");
@@ -74,4 +104,34 @@ mod tests {
         assert_eq!(card.priority, 1);
         assert_eq!(card._type, "primary");
     }
+
+    #[test]
+    fn test_extra() {
+        let s = r#"
+          {
+            "cc_type": "Visa",
+            "cc_last_4": "1234",
+            "exp_month": "01",
+            "exp_year": "24",
+            "cc_exp": "01/24",
+            "cc_priority": "1",
+            "cc_id": "123456",
+            "type": "primary",
+            "expired": false
+          }
+        "#;
+
+        let card: Card = serde_json::from_str(s).unwrap();
+
+        #[cfg(feature = "drift")]
+        {
+            assert_eq!(card.extra.len(), 2);
+            assert_eq!(card.extra["exp_month"], "01");
+            assert_eq!(card.extra["exp_year"], "24");
+        }
+
+        // The keys are only kept with the `drift` feature
+        #[cfg(not(feature = "drift"))]
+        assert!(card.extra.is_empty());
+    }
 }
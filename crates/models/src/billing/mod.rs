@@ -4,5 +4,11 @@ pub use card::*;
 mod transaction;
 pub use transaction::*;
 
+mod transaction_entry;
+pub use transaction_entry::*;
+
+mod transaction_type;
+pub use transaction_type::*;
+
 mod billing_data;
 pub use billing_data::*;
@@ -1,23 +1,34 @@
+use std::collections::BTreeMap;
+
 use chrono::DateTime;
 use chrono_tz::Tz;
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::{de, se, OrderId};
+
+use super::{TransactionEntry, TransactionType};
 
-use crate::de;
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
 
 // A transaction represents a completed payment
 // on the platform
 #[allow(dead_code)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Transaction {
 
     // The order ID to which the transaction
     // corresponds to
     #[serde(rename = "order_id")]
-    pub order_id: String,
+    pub order_id: OrderId,
 
     // The final total amount charged in the transaction
-    #[serde(rename = "total", deserialize_with = "de::money")]
+    #[serde(
+        rename = "total",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub total: Money<'static, iso::Currency>,
 
     // A short title describing the transaction
@@ -27,21 +38,27 @@ pub struct Transaction {
     // The date at which the transaction was processed
     #[serde(
         rename = "transaction_time",
-        deserialize_with = "de::timestamp"
+        deserialize_with = "de::timestamp",
+        serialize_with = "se::timestamp"
     )]
     pub timestamp: DateTime<Tz>,
 
-    // TODO -- not sure
+    // The type of the transaction, e.g. a credit card payment
     #[serde(rename = "transaction_type")]
-    pub _type: String,
+    pub _type: TransactionType,
 
+    // The charges and credits that make up the transaction,
+    // in the order they were processed
+    #[serde(default, rename = "transactions", deserialize_with = "entries")]
+    pub entries: Vec<TransactionEntry>,
 
     // The total amount estimated to be charged before packing
     // and delivery
     #[serde(
         default,
         rename = "total_order_amount",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub total_order_amount: Option<Money<'static, iso::Currency>>,
 
@@ -50,7 +67,8 @@ pub struct Transaction {
     #[serde(
         default,
         rename = "basket_cost",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub basket_cost: Option<Money<'static, iso::Currency>>,
 
@@ -58,7 +76,8 @@ pub struct Transaction {
     #[serde(
         default,
         rename = "previous_amount_due",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub previous_amount_due: Option<Money<'static, iso::Currency>>,
 
@@ -66,7 +85,8 @@ pub struct Transaction {
     #[serde(
         default,
         rename = "donation_amount",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub donation_amount: Option<Money<'static, iso::Currency>>,
 
@@ -74,7 +94,8 @@ pub struct Transaction {
     #[serde(
         default,
         rename = "charity_received",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub charity_received: Option<Money<'static, iso::Currency>>,
 
@@ -82,14 +103,57 @@ pub struct Transaction {
     #[serde(
         default,
         rename = "total_consigne_amount",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub total_consigne_amount: Option<Money<'static, iso::Currency>>,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+// Deserializes the entries of a transaction, which Lufa keys by
+// their type code, ordered by time and then by type code
+fn entries<'de, D>(deserializer: D) -> Result<Vec<TransactionEntry>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let entries: BTreeMap<usize, TransactionEntry> =
+        de::array_or_object(deserializer)?.into_iter().collect();
+
+    let mut entries: Vec<TransactionEntry> = entries.into_values().collect();
+    entries.sort_by_key(|entry| entry.timestamp);
+
+    Ok(entries)
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for Transaction {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "Transaction",
+        fields: &[
+            Field::required("order_id", drift::STRING),
+            Field::required("total", drift::NUMERIC),
+            Field::required("title_string", drift::STRING),
+            Field::required("transaction_time", drift::STRING),
+            Field::required("transaction_type", drift::NUMERIC),
+            Field::optional("transactions", drift::ARRAY_OR_OBJECT),
+            Field::optional("total_order_amount", drift::NUMERIC_OR_NULL),
+            Field::optional("basket_cost", drift::NUMERIC_OR_NULL),
+            Field::optional("previous_amount_due", drift::NUMERIC_OR_NULL),
+            Field::optional("donation_amount", drift::NUMERIC_OR_NULL),
+            Field::optional("charity_received", drift::NUMERIC_OR_NULL),
+            Field::optional("total_consigne_amount", drift::NUMERIC_OR_NULL),
+        ],
+    };
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Transaction;
+    use rusty_money::{iso, Money};
+
+    use super::{Transaction, TransactionType};
 
     #[test]
     fn test_deserialize() {
@@ -157,5 +221,15 @@ mod tests {
 
         let tx: Result<Transaction, _> = serde_json::from_str(s);
         assert!(tx.is_ok());
+
+        assert_eq!(tx.as_ref().unwrap()._type, TransactionType::CardPayment);
+
+        let entries = &tx.as_ref().unwrap().entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]._type, TransactionType::RemainingCreditsPayment);
+        assert_eq!(entries[0].amount, Money::from_minor(-418, iso::CAD));
+        assert_eq!(entries[1].product_name.as_deref(), Some("Champignons shiitakes"));
+
+        crate::se::assert_roundtrip(tx.as_ref().unwrap());
     }
 }
@@ -0,0 +1,125 @@
+use chrono::DateTime;
+use chrono_tz::Tz;
+use rusty_money::{iso, Money};
+use serde::{Deserialize, Serialize};
+
+use crate::{de, se, OrderId, TransactionId};
+
+use super::TransactionType;
+
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
+
+// A TransactionEntry is one of the charges or credits
+// that make up a transaction, e.g. the credit card payment
+// of an order and the weight adjustments of its products
+#[allow(dead_code)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TransactionEntry {
+
+    // The ID of the entry
+    #[serde(rename = "transaction_id")]
+    pub id: TransactionId,
+
+    // The order ID to which the entry corresponds to
+    #[serde(rename = "transaction_order_id")]
+    pub order_id: OrderId,
+
+    // The type of the entry, e.g. a weight adjustment
+    #[serde(rename = "transaction_type")]
+    pub _type: TransactionType,
+
+    // The amount charged (negative) or credited (positive)
+    // by the entry
+    #[serde(
+        rename = "transaction_amount",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
+    pub amount: Money<'static, iso::Currency>,
+
+    // The date at which the entry was processed
+    #[serde(
+        rename = "transaction_time",
+        deserialize_with = "de::timestamp",
+        serialize_with = "se::timestamp"
+    )]
+    pub timestamp: DateTime<Tz>,
+
+    // The last four digits of the card charged, if any
+    #[serde(default, rename = "transaction_last_4")]
+    pub last_four: Option<String>,
+
+    // The product the entry applies to, if any, e.g.
+    // for weight adjustments
+    #[serde(default, rename = "product_name")]
+    pub product_name: Option<String>,
+
+    // Lufa's label for the entry's type, in the language of
+    // the session, e.g. "Product price adjustments based on
+    // weight". See `TransactionType::label_en` for a label
+    // in a given language.
+    #[serde(rename = "transaction_type_formatted")]
+    pub description: String,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for TransactionEntry {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "TransactionEntry",
+        fields: &[
+            Field::required("transaction_id", drift::STRING),
+            Field::required("transaction_order_id", drift::STRING),
+            Field::required("transaction_type", drift::NUMERIC),
+            Field::required("transaction_amount", drift::NUMERIC),
+            Field::required("transaction_time", drift::STRING),
+            Field::optional("transaction_last_4", drift::STRING_OR_NULL),
+            Field::optional("product_name", drift::STRING_OR_NULL),
+            Field::required("transaction_type_formatted", drift::STRING),
+        ],
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use rusty_money::{iso, Money};
+
+    use super::{TransactionEntry, TransactionType};
+
+    #[test]
+    fn test_deserialize() {
+        let s = r#"
+          {
+            "transaction_id": "12345678",
+            "transaction_order_id": "12345678",
+            "transaction_type": 38,
+            "transaction_amount": -1.05,
+            "transaction_time": "2024-05-07 14:38:31",
+            "transaction_last_4": null,
+            "total_order_amount": "140.91",
+            "donation_amount": "0.00",
+            "total_consigne_amount": "0.25",
+            "charity_received": "0.00",
+            "previous_amount_due": "0.00",
+            "basket_cost": "140.66000366210938",
+            "product_name": "Champignons shiitakes",
+            "transaction_amount_formatted": " - $ 1.05",
+            "transaction_date": "2024-05-07",
+            "transaction_type_formatted": "Product price adjustments based on weight"
+          }
+        "#;
+
+        let entry: TransactionEntry = serde_json::from_str(s).unwrap();
+        assert_eq!(entry.order_id, "12345678");
+        assert_eq!(entry._type, TransactionType::WeightAdjustment);
+        assert_eq!(entry.amount, Money::from_minor(-105, iso::CAD));
+        assert_eq!(entry.product_name.as_deref(), Some("Champignons shiitakes"));
+        assert_eq!(entry.last_four, None);
+
+        crate::se::assert_roundtrip(&entry);
+    }
+}
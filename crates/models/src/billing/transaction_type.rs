@@ -0,0 +1,216 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// The type of a transaction or of one of its entries,
+// as identified by its numeric code.
+//
+// Only the codes seen in Lufa's payloads are mapped. The
+// codes of donations and refunds have not been observed, and
+// are deliberately not guessed, since a wrong guess would
+// silently misfile transactions: `Donation` and `Refund` have
+// no code, and are never received from Lufa until their codes
+// are known. Unmapped codes are kept as `Unknown(code)`, and
+// their entries still carry Lufa's own label in
+// `TransactionEntry::description`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransactionType {
+    // The part of a basket order paid with the user's credits
+    RemainingCreditsPayment,
+
+    // The part of a basket order charged to a credit card
+    CardPayment,
+
+    // A credit or charge adjusting the price of a product
+    // sold by weight, once it has been packed
+    WeightAdjustment,
+
+    // A donation made by the user. Never received from Lufa,
+    // see above.
+    Donation,
+
+    // A refund made to the user. Never received from Lufa,
+    // see above.
+    Refund,
+
+    // A transaction type not known to the client
+    Unknown(u32),
+
+    // A transaction type not known to the client that is not
+    // a code, kept as sent
+    UnknownName(String),
+}
+
+impl TransactionType {
+    // The numeric code of the type, as sent by Lufa,
+    // if known
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            TransactionType::RemainingCreditsPayment => Some(20),
+            TransactionType::CardPayment => Some(21),
+            TransactionType::WeightAdjustment => Some(38),
+            TransactionType::Unknown(code) => Some(*code),
+            _ => None,
+        }
+    }
+
+    // The name of the type, for the types without a code,
+    // e.g. "donation"
+    pub fn name(&self) -> &str {
+        match self {
+            TransactionType::RemainingCreditsPayment => "remaining_credits_payment",
+            TransactionType::CardPayment => "card_payment",
+            TransactionType::WeightAdjustment => "weight_adjustment",
+            TransactionType::Donation => "donation",
+            TransactionType::Refund => "refund",
+            TransactionType::Unknown(_) => "unknown",
+            TransactionType::UnknownName(name) => name,
+        }
+    }
+
+    // Whether the type is a payment of an order, rather
+    // than an adjustment of it
+    pub fn is_payment(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::RemainingCreditsPayment | TransactionType::CardPayment
+        )
+    }
+
+    // A label for the type, in English. Lufa's own label is
+    // used when given, i.e. the `description` of an entry
+    // fetched in English, and a label of the client's
+    // otherwise. None when neither is known.
+    pub fn label_en<'a>(&self, formatted: Option<&'a str>) -> Option<&'a str> {
+        let fallback = match self {
+            TransactionType::RemainingCreditsPayment => Some("Basket Order - Remaining credits payment"),
+            TransactionType::CardPayment => Some("Basket Order - Credit card payment"),
+            TransactionType::WeightAdjustment => Some("Product price adjustments based on weight"),
+            TransactionType::Donation => Some("Donation"),
+            TransactionType::Refund => Some("Refund"),
+            _ => None,
+        };
+
+        label(formatted, fallback)
+    }
+
+    // A label for the type, in French, see `label_en`
+    pub fn label_fr<'a>(&self, formatted: Option<&'a str>) -> Option<&'a str> {
+        let fallback = match self {
+            TransactionType::RemainingCreditsPayment => Some("Commande de panier - Paiement des crédits restants"),
+            TransactionType::CardPayment => Some("Commande de panier - Paiement par carte de crédit"),
+            TransactionType::WeightAdjustment => Some("Ajustements de prix des produits selon le poids"),
+            TransactionType::Donation => Some("Don"),
+            TransactionType::Refund => Some("Remboursement"),
+            _ => None,
+        };
+
+        label(formatted, fallback)
+    }
+}
+
+// Prefers Lufa's label over the client's, unless it is blank
+fn label<'a>(formatted: Option<&'a str>, fallback: Option<&'static str>) -> Option<&'a str> {
+    formatted
+        .map(str::trim)
+        .filter(|formatted| !formatted.is_empty())
+        .or(fallback)
+}
+
+impl From<u32> for TransactionType {
+    fn from(code: u32) -> Self {
+        match code {
+            20 => TransactionType::RemainingCreditsPayment,
+            21 => TransactionType::CardPayment,
+            38 => TransactionType::WeightAdjustment,
+            code => TransactionType::Unknown(code),
+        }
+    }
+}
+
+// Lufa sends the code as either a number or a string,
+// e.g. `38` or `"20"`. The names of the types without a
+// code are accepted as well, and any other string is kept
+// as `UnknownName`, rather than failing the transactions.
+impl<'de> Deserialize<'de> for TransactionType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Code {
+            Number(u32),
+            String(String),
+        }
+
+        let s = match Code::deserialize(deserializer)? {
+            Code::Number(n) => return Ok(TransactionType::from(n)),
+            Code::String(s) => s,
+        };
+
+        if let Ok(code) = s.trim().parse::<u32>() {
+            return Ok(TransactionType::from(code));
+        }
+
+        match s.trim() {
+            "donation" => Ok(TransactionType::Donation),
+            "refund" => Ok(TransactionType::Refund),
+            _ => Ok(TransactionType::UnknownName(s)),
+        }
+    }
+}
+
+// Serialized as a string, the way Lufa mostly sends it:
+// its code when known, or else its name
+impl Serialize for TransactionType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.code() {
+            Some(code) => serializer.collect_str(&code),
+            None => serializer.serialize_str(self.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionType;
+
+    #[test]
+    fn test_transaction_type() {
+        let t: TransactionType = serde_json::from_str(r#""20""#).unwrap();
+        assert_eq!(t, TransactionType::RemainingCreditsPayment);
+        assert!(t.is_payment());
+
+        let t: TransactionType = serde_json::from_str("38").unwrap();
+        assert_eq!(t, TransactionType::WeightAdjustment);
+        assert!(!t.is_payment());
+        assert_eq!(t.label_fr(None), Some("Ajustements de prix des produits selon le poids"));
+        assert_eq!(t.label_en(Some("Weight adjustments")), Some("Weight adjustments"));
+        assert_eq!(t.label_en(Some(" ")), Some("Product price adjustments based on weight"));
+
+        let t: TransactionType = serde_json::from_str(r#""99""#).unwrap();
+        assert_eq!(t, TransactionType::Unknown(99));
+        assert_eq!(t.code(), Some(99));
+        assert_eq!(t.label_en(None), None);
+        assert_eq!(t.label_en(Some("Gift card")), Some("Gift card"));
+        assert_eq!(serde_json::to_string(&t).unwrap(), r#""99""#);
+
+        for t in [TransactionType::Donation, TransactionType::Refund] {
+            let json = serde_json::to_string(&t).unwrap();
+            assert_eq!(json, format!("\"{}\"", t.name()));
+            assert_eq!(serde_json::from_str::<TransactionType>(&json).unwrap(), t);
+            assert_eq!(t.code(), None);
+        }
+
+        // A type that is not a code does not fail the
+        // transactions, and is kept as sent
+        for s in ["card", ""] {
+            let json = format!("\"{}\"", s);
+            let t: TransactionType = serde_json::from_str(&json).unwrap();
+            assert_eq!(t, TransactionType::UnknownName(s.to_string()));
+            assert_eq!(serde_json::to_string(&t).unwrap(), json);
+        }
+    }
+}
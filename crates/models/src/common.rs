@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{OrderId, UserId};
+
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 pub struct ApiResponse<T> {
@@ -11,11 +13,11 @@ pub struct ApiResponse<T> {
 #[derive(Debug, Serialize)]
 pub struct PerUserForm {
     #[serde(rename = "user_id")]
-    pub user_id: String,
+    pub user_id: UserId,
 }
 
 #[derive(Debug, Serialize)]
 pub struct PerOrderForm {
     #[serde(rename = "order_id")]
-    pub order_id: String,
+    pub order_id: OrderId,
 }
@@ -12,15 +12,20 @@ where
     D: Deserializer<'de>,
     T: Deserialize<'de> + Clone,
 {
+    // The keys of an object are strings once buffered by
+    // the untagged enum, so they are parsed separately
     #[derive(Deserialize)]
     #[serde(untagged)]
     enum ArrayOrObject<T> {
         Array(Vec<T>),
-        Object(HashMap<usize, T>),
+        Object(HashMap<String, T>),
     }
 
     match ArrayOrObject::<T>::deserialize(deserializer)? {
-        ArrayOrObject::Object(o) => Ok(o),
+        ArrayOrObject::Object(o) => o
+            .into_iter()
+            .map(|(k, v)| Ok((k.parse().map_err(serde::de::Error::custom)?, v)))
+            .collect(),
         ArrayOrObject::Array(a) => Ok(a.iter().cloned().enumerate().collect()),
     }
 }
@@ -1,5 +1,6 @@
 // Deserializers for money/currency strings.
 
+use rust_decimal::Decimal;
 use rusty_money::{iso, Money};
 use serde::{Deserialize, Deserializer};
 
@@ -23,6 +24,16 @@ where
     Money::from_str(&cleaned, iso::CAD).map_err(serde::de::Error::custom)
 }
 
+// Function to parse a Money instance from a decimal string with
+// more decimals than the currency has, e.g. "140.66000366210938"
+fn parse_precise<'de, D>(s: &str) -> Result<Money<'static, iso::Currency>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let amount: Decimal = s.parse().map_err(serde::de::Error::custom)?;
+    Ok(Money::from_decimal(amount, iso::CAD))
+}
+
 // Function to parse a Money instance from either a North American or
 // European styled currency string. Examples below:
 //
@@ -38,10 +49,10 @@ where
 {
     let cleaned: String = s
         .chars()
-        .filter(|&c| c.is_digit(10) || c == '.' || c == ',' || c == '-' || c == '+')
+        .filter(|&c| c.is_ascii_digit() || c == '.' || c == ',' || c == '-' || c == '+')
         .collect();
 
-    if cleaned.len() == 0 {
+    if cleaned.is_empty() {
         return Err(serde::de::Error::custom("empty string"));
     }
 
@@ -55,10 +66,12 @@ where
             // Get number of characters after dot
             let n = cleaned.len() - (cleaned.find(".").unwrap() + 1);
 
+            // Four or more decimals cannot be a thousands
+            // separator, e.g. "140.66000366210938"
             match n {
-                0 | 1 | 2 => parse_na::<D>(&cleaned),
+                0..=2 => parse_na::<D>(&cleaned),
                 3 => parse_eu::<D>(&cleaned),
-                _ => Err(serde::de::Error::custom("malformed currency string")),
+                _ => parse_precise::<D>(&cleaned),
             }
         }
         (0, 1) => {
@@ -66,9 +79,9 @@ where
             let n = cleaned.len() - (cleaned.find(",").unwrap() + 1);
 
             match n {
-                0 | 1 | 2 => parse_eu::<D>(&cleaned),
+                0..=2 => parse_eu::<D>(&cleaned),
                 3 => parse_na::<D>(&cleaned),
-                _ => Err(serde::de::Error::custom("malformed currency string")),
+                _ => parse_precise::<D>(&cleaned.replace(",", ".")),
             }
         }
         (1, 1) => {
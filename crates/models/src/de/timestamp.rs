@@ -25,21 +25,71 @@ where
     }
 
     match DateOrNull::deserialize(deserializer)? {
-        DateOrNull::String(s) => {
-            let pattern = if s.chars().next().unwrap().is_alphabetic() {
-                "%B %d, %Y"
-            } else {
-                "%Y-%m-%d"
-            };
-
-            NaiveDate::parse_from_str(&s, pattern)
-                .map(Some)
-                .map_err(serde::de::Error::custom)
-        }
+        DateOrNull::String(s) => parse_date(&s)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date: {:?}", s))),
         DateOrNull::Null => Ok(None),
     }
 }
 
+const MONTHS: [&[&str]; 12] = [
+    &["january", "janvier"],
+    &["february", "février", "fevrier"],
+    &["march", "mars"],
+    &["april", "avril"],
+    &["may", "mai"],
+    &["june", "juin"],
+    &["july", "juillet"],
+    &["august", "août", "aout"],
+    &["september", "septembre"],
+    &["october", "octobre"],
+    &["november", "novembre"],
+    &["december", "décembre", "decembre"],
+];
+
+const WEEKDAYS: [&str; 14] = [
+    "monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday",
+    "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+];
+
+// Parses a date in any of the forms Lufa sends them, in
+// either English or French. Examples below:
+//
+// `"2024-05-12"`
+// `"May 12, 2024"`
+// `"Sunday, May 12th, 2024"`
+// `"dimanche 12 mai 2024"`
+// `"Mercredi, 1er mai 2024"`
+pub(crate) fn parse_date(s: &str) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let (mut year, mut month, mut day) = (None, None, None);
+
+    for token in s.to_lowercase().split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() || WEEKDAYS.contains(&token) {
+            continue;
+        }
+
+        if let Some(m) = MONTHS.iter().position(|names| names.contains(&token)) {
+            month = Some(m as u32 + 1);
+            continue;
+        }
+
+        // Days may carry an ordinal suffix, e.g. "12th", "1er"
+        let digits = token.trim_end_matches(|c: char| c.is_alphabetic());
+        let n: u32 = digits.parse().ok()?;
+
+        match digits.len() {
+            4 => year = Some(n as i32),
+            _ => day = Some(n),
+        }
+    }
+
+    NaiveDate::from_ymd_opt(year?, month?, day?)
+}
+
 pub fn timestamp<'de, D>(deserializer: D) -> Result<DateTime<Tz>, D::Error>
 where
     D: Deserializer<'de>,
@@ -84,3 +134,28 @@ where
         TimestampOrNull::Null => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::parse_date;
+
+    #[test]
+    fn test_parse_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 12);
+
+        assert_eq!(parse_date("2024-05-12"), date);
+        assert_eq!(parse_date("May 12, 2024"), date);
+        assert_eq!(parse_date("Sunday, May 12th, 2024"), date);
+        assert_eq!(parse_date("dimanche 12 mai 2024"), date);
+        assert_eq!(parse_date("Dimanche, 12 mai 2024"), date);
+        assert_eq!(parse_date("Mercredi, 1er mai 2024"), NaiveDate::from_ymd_opt(2024, 5, 1));
+        assert_eq!(parse_date("12 août 2024"), NaiveDate::from_ymd_opt(2024, 8, 12));
+
+        assert_eq!(parse_date(""), None);
+        assert_eq!(parse_date("May 2024"), None);
+        assert_eq!(parse_date("Someday, May 12th, 2024"), None);
+        assert_eq!(parse_date("February 30, 2024"), None);
+    }
+}
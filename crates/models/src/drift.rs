@@ -0,0 +1,397 @@
+use std::fmt;
+
+use serde_json::Value;
+
+pub use crate::Extra;
+
+// The type of a JSON value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Bool,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JsonType::Null => "null",
+            JsonType::Bool => "bool",
+            JsonType::Number => "number",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        };
+
+        f.write_str(name)
+    }
+}
+
+// The types accepted by the models' fields. Amounts and
+// counts are sent as either numbers or strings.
+pub(crate) const STRING: &[JsonType] = &[JsonType::String];
+pub(crate) const STRING_OR_NULL: &[JsonType] = &[JsonType::String, JsonType::Null];
+pub(crate) const BOOL: &[JsonType] = &[JsonType::Bool];
+pub(crate) const NUMBER: &[JsonType] = &[JsonType::Number];
+pub(crate) const NUMERIC: &[JsonType] = &[JsonType::Number, JsonType::String];
+pub(crate) const NUMERIC_OR_NULL: &[JsonType] =
+    &[JsonType::Number, JsonType::String, JsonType::Null];
+pub(crate) const ARRAY: &[JsonType] = &[JsonType::Array];
+pub(crate) const OBJECT: &[JsonType] = &[JsonType::Object];
+pub(crate) const OBJECT_OR_NULL: &[JsonType] = &[JsonType::Object, JsonType::Null];
+pub(crate) const ARRAY_OR_OBJECT: &[JsonType] = &[JsonType::Array, JsonType::Object];
+
+// A field of a model, as Lufa is known to send it
+#[derive(Debug)]
+pub struct Field {
+    pub name: &'static str,
+
+    // The JSON types the model accepts for the field
+    pub types: &'static [JsonType],
+
+    // Whether parsing fails without the field
+    pub required: bool,
+
+    // The schema of the field's value, if it is itself
+    // a model that is checked for drift
+    pub schema: Option<&'static Schema>,
+}
+
+impl Field {
+    pub(crate) const fn required(name: &'static str, types: &'static [JsonType]) -> Self {
+        Field {
+            name,
+            types,
+            required: true,
+            schema: None,
+        }
+    }
+
+    pub(crate) const fn optional(name: &'static str, types: &'static [JsonType]) -> Self {
+        Field {
+            name,
+            types,
+            required: false,
+            schema: None,
+        }
+    }
+
+    pub(crate) const fn nested(self, schema: &'static Schema) -> Self {
+        Field {
+            schema: Some(schema),
+            ..self
+        }
+    }
+}
+
+// The fields of a model as Lufa is known to send them
+#[derive(Debug)]
+pub struct Schema {
+    pub model: &'static str,
+    pub fields: &'static [Field],
+}
+
+impl Schema {
+    // Compares a JSON object against the schema
+    pub fn report(&'static self, value: &Value) -> DriftReport {
+        let mut drifts = Vec::new();
+        self.compare("", value, &mut drifts);
+
+        DriftReport {
+            model: self.model,
+            drifts,
+        }
+    }
+
+    fn compare(&self, prefix: &str, value: &Value, drifts: &mut Vec<Drift>) {
+        let Value::Object(object) = value else {
+            drifts.push(Drift::ChangedType {
+                path: prefix.trim_end_matches('.').to_string(),
+                expected: OBJECT,
+                found: JsonType::of(value),
+            });
+            return;
+        };
+
+        for field in self.fields {
+            let path = format!("{}{}", prefix, field.name);
+
+            match object.get(field.name) {
+                None if field.required => drifts.push(Drift::Missing { path }),
+                None => {}
+                Some(value) if !field.types.contains(&JsonType::of(value)) => {
+                    drifts.push(Drift::ChangedType {
+                        path,
+                        expected: field.types,
+                        found: JsonType::of(value),
+                    })
+                }
+                Some(value) => {
+                    if let Some(schema) = field.schema {
+                        schema.compare(&format!("{}.", path), value, drifts);
+                    }
+                }
+            }
+        }
+
+        for (key, value) in object {
+            if self.fields.iter().all(|field| field.name != key) {
+                drifts.push(Drift::New {
+                    path: format!("{}{}", prefix, key),
+                    found: JsonType::of(value),
+                });
+            }
+        }
+    }
+}
+
+// A model whose schema is known, and can be checked for drift
+pub trait KnownSchema {
+    const SCHEMA: &'static Schema;
+}
+
+// A difference between what Lufa sent and what a model expects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    // A field the model does not know about
+    New { path: String, found: JsonType },
+
+    // A required field that was not sent
+    Missing { path: String },
+
+    // A field sent with a type the model does not accept
+    ChangedType {
+        path: String,
+        expected: &'static [JsonType],
+        found: JsonType,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::New { path, found } => write!(f, "new field `{}` ({})", path, found),
+            Drift::Missing { path } => write!(f, "missing field `{}`", path),
+            Drift::ChangedType {
+                path,
+                expected,
+                found,
+            } => {
+                let expected: Vec<String> = expected.iter().map(|t| t.to_string()).collect();
+                write!(
+                    f,
+                    "field `{}` changed type: expected {}, found {}",
+                    path,
+                    expected.join(" or "),
+                    found
+                )
+            }
+        }
+    }
+}
+
+// The differences between a JSON object sent by Lufa and
+// the schema of the model it is parsed into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriftReport {
+    pub model: &'static str,
+    pub drifts: Vec<Drift>,
+}
+
+impl DriftReport {
+    pub fn is_empty(&self) -> bool {
+        self.drifts.is_empty()
+    }
+
+    // Whether the model would fail to parse the object,
+    // rather than only ignore some of it
+    pub fn is_breaking(&self) -> bool {
+        self.drifts
+            .iter()
+            .any(|drift| !matches!(drift, Drift::New { .. }))
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "{}: no drift", self.model);
+        }
+
+        write!(f, "{}:", self.model)?;
+        for drift in &self.drifts {
+            write!(f, "\n  - {}", drift)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Compares a JSON object sent by Lufa against the known
+// schema of the model it is parsed into, e.g.
+//
+// `drift_report::<Order>(&value)`
+pub fn drift_report<M: KnownSchema>(value: &Value) -> DriftReport {
+    M::SCHEMA.report(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::{json, Value};
+
+    use super::{drift_report, Drift, Extra, JsonType, KnownSchema, NUMERIC};
+    use crate::{
+        billing::{Card, Transaction, TransactionEntry},
+        orders::{CheckoutAmounts, Order, OrderTracking},
+        profile::Profile,
+    };
+
+    fn card() -> serde_json::Value {
+        json!({
+            "cc_type": "Visa",
+            "cc_last_4": "1234",
+            "cc_exp": "01/24",
+            "cc_priority": "1",
+            "cc_id": "123456",
+            "type": "primary",
+            "expired": false
+        })
+    }
+
+    #[test]
+    fn test_no_drift() {
+        let report = drift_report::<Card>(&card());
+        assert!(report.is_empty());
+        assert_eq!(report.to_string(), "Card: no drift");
+    }
+
+    #[test]
+    fn test_drift() {
+        let mut card = card();
+        let object = card.as_object_mut().unwrap();
+        object.remove("cc_id");
+        object.insert("cc_priority".to_string(), json!(null));
+        object.insert("exp_year".to_string(), json!("24"));
+
+        let report = drift_report::<Card>(&card);
+        assert!(report.is_breaking());
+        assert_eq!(
+            report.drifts,
+            vec![
+                Drift::Missing {
+                    path: "cc_id".to_string(),
+                },
+                Drift::ChangedType {
+                    path: "cc_priority".to_string(),
+                    expected: NUMERIC,
+                    found: JsonType::Null,
+                },
+                Drift::New {
+                    path: "exp_year".to_string(),
+                    found: JsonType::String,
+                },
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Card:\n  - missing field `cc_id`\n  - field `cc_priority` changed type: expected number or string, found null\n  - new field `exp_year` (string)"
+        );
+    }
+
+    #[test]
+    fn test_nested_drift() {
+        let order = json!({
+            "orderId": "12345678",
+            "orderStatus": "4",
+            "orderDate": "2024-05-12",
+            "orderDetails": [],
+            "orderRecipes": [],
+            "checkoutAmounts": { "total": "137.95", "currency": "CAD" }
+        });
+
+        let report = drift_report::<Order>(&order);
+        assert!(report.is_breaking());
+        assert!(report.drifts.contains(&Drift::Missing {
+            path: "checkoutAmounts.subtotal".to_string()
+        }));
+        assert!(report.drifts.contains(&Drift::New {
+            path: "checkoutAmounts.currency".to_string(),
+            found: JsonType::String,
+        }));
+    }
+
+    #[test]
+    fn test_not_an_object() {
+        let report = drift_report::<Card>(&json!([]));
+        assert_eq!(
+            report.drifts,
+            vec![Drift::ChangedType {
+                path: String::new(),
+                expected: super::OBJECT,
+                found: JsonType::Array,
+            }]
+        );
+    }
+
+    // Checks that a model's schema lists exactly the keys
+    // the model maps, i.e. the keys it serializes that are
+    // not part of its extra fields
+    fn assert_schema_in_sync<M>(value: &Value, extra: fn(&M) -> &Extra)
+    where
+        M: KnownSchema + DeserializeOwned + Serialize,
+    {
+        let model: M = serde_json::from_value(value.clone()).unwrap();
+        let serialized = serde_json::to_value(&model).unwrap();
+
+        let mapped: BTreeSet<&str> = serialized
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !extra(&model).contains_key(*key))
+            .collect();
+        let known: BTreeSet<&str> = M::SCHEMA.fields.iter().map(|field| field.name).collect();
+
+        assert_eq!(mapped, known, "schema of {} is out of sync", M::SCHEMA.model);
+    }
+
+    #[test]
+    fn test_schemas_in_sync() {
+        let fixture = |s: &str| -> Value { serde_json::from_str(s).unwrap() };
+
+        let order = fixture(include_str!("../tests/fixtures/order.json"));
+        let tracking = fixture(include_str!("../tests/fixtures/tracking.json"));
+        let profile = fixture(include_str!("../tests/fixtures/profile.json"));
+        let billing = fixture(include_str!("../tests/fixtures/billing.json"));
+
+        assert_schema_in_sync::<Order>(&order, |m| &m.extra);
+        assert_schema_in_sync::<CheckoutAmounts>(&order["checkoutAmounts"], |m| &m.extra);
+        assert_schema_in_sync::<OrderTracking>(&tracking, |m| &m.extra);
+        assert_schema_in_sync::<Profile>(&profile, |m| &m.extra);
+        assert_schema_in_sync::<Card>(&billing["cards"]["0"], |m| &m.extra);
+        assert_schema_in_sync::<Transaction>(&billing["transactions"][0], |m| &m.extra);
+        assert_schema_in_sync::<TransactionEntry>(
+            &billing["transactions"][0]["transactions"]["20"],
+            |m| &m.extra,
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+// The keys of a model that it does not map, as sent by Lufa.
+//
+// Every model with a known schema has an `extra` field of
+// this type. With the `drift` feature, the keys the model
+// does not map are flattened into it, so that nothing the
+// API sends is lost and changes to the API can be detected,
+// and they are written back as is when the model is
+// serialized. Without it, the field is always empty.
+pub type Extra = BTreeMap<String, Value>;
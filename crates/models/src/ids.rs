@@ -0,0 +1,111 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// Declares a newtype around the string ID of an entity,
+// so that the IDs of different entities cannot be mixed up
+macro_rules! id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new<S: Into<String>>(id: S) -> Self {
+                Self(id.into())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+id! {
+    /// The ID of an order, e.g. "12345678"
+    OrderId
+}
+
+id! {
+    /// The ID of a product on the marketplace
+    ProductId
+}
+
+id! {
+    /// The ID of a meal kit recipe
+    RecipeId
+}
+
+id! {
+    /// The ID of a saved payment card
+    CardId
+}
+
+id! {
+    /// The ID of a user
+    UserId
+}
+
+id! {
+    /// The ID of a billing transaction
+    TransactionId
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderId, ProductId};
+
+    #[test]
+    fn test_ids() {
+        let id: OrderId = serde_json::from_str(r#""12345678""#).unwrap();
+        assert_eq!(id, "12345678");
+        assert_eq!(id.to_string(), "12345678");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""12345678""#);
+
+        let product = ProductId::new("12345678");
+        assert_eq!(product.as_str(), id.as_str());
+        assert_eq!(id, OrderId::from(product.into_inner()));
+    }
+}
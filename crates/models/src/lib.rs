@@ -3,9 +3,20 @@ pub mod billing;
 pub mod cookies;
 pub mod profile;
 pub mod orders;
+pub mod analysis;
+
+#[cfg(feature = "drift")]
+pub mod drift;
 
 mod common;
 pub use common::*;
 
+mod ids;
+pub use ids::*;
+
+mod extra;
+pub use extra::*;
+
 pub(crate) mod de;
+pub(crate) mod se;
 pub(crate) mod utils;
@@ -1,18 +1,25 @@
 use std::collections::HashMap;
 
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se};
+
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
 
 // A CheckoutAmountsItem contains individual
 // pricing information per item in the order.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct CheckoutAmountsItem {
 
     // The base price of the item, per one item
-    #[serde(rename = "price", deserialize_with = "de::money")]
+    #[serde(
+        rename = "price",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub price: Money<'static, iso::Currency>,
 
     // The quantity of item present in the order
@@ -23,7 +30,11 @@ pub struct CheckoutAmountsItem {
     pub quantity: usize,
 
     // The total cost of the item, calculated as price * quanity
-    #[serde(rename = "row_total", deserialize_with = "de::money")]
+    #[serde(
+        rename = "row_total",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub total: Money<'static, iso::Currency>,
 }
 
@@ -32,85 +43,110 @@ pub struct CheckoutAmountsItem {
 // including total, subtotal, taxes, fees, coupons,
 // as well as an itemized list of the pricing of each
 // item in the order.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct CheckoutAmounts {
     // The total cost of the order
-    #[serde(rename = "total", deserialize_with = "de::money")]
+    #[serde(
+        rename = "total",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub total: Money<'static, iso::Currency>,
 
     // The total cost of the items in order prior
     // to taxes, coupons, etc.
-    #[serde(rename = "subtotal", deserialize_with = "de::money")]
+    #[serde(
+        rename = "subtotal",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub subtotal: Money<'static, iso::Currency>,
 
     // The cost of delivery fees for the order
     #[serde(
         rename = "delivery_fees",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub delivery_fees: Money<'static, iso::Currency>,
 
     // The amount that the user has to pay off
     #[serde(
         rename = "remaining_balance",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub remaining_balance: Money<'static, iso::Currency>,
 
     // // TODO -- not sure what this is
-    #[serde(rename = "balance", deserialize_with = "de::money")]
+    #[serde(
+        rename = "balance",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub balance: Money<'static, iso::Currency>,
 
     /// TODO -- not sure what this is
     #[serde(
         rename = "consigne_amount",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub consigne_amount: Money<'static, iso::Currency>,
 
     // The national sales tax (GST + HST) for the order 
-    #[serde(rename = "national_tax", deserialize_with = "de::money")]
+    #[serde(
+        rename = "national_tax",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub national_tax: Money<'static, iso::Currency>,
 
     // The provincial sales tax (PST) for the order 
     #[serde(
         rename = "provincial_tax",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub provincial_tax: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "coupon_discount_amount",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub coupon_discount_amount: Money<'static, iso::Currency>,
 
     // The contribution/donation amount selected by the user
     #[serde(
         rename = "order_donation",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub order_donation: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "donation_discount",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub donation_discount: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "available_weekly",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub available_weekly: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "remaining_weekly",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub remaining_weekly: Money<'static, iso::Currency>,
 
@@ -118,6 +154,33 @@ pub struct CheckoutAmounts {
     // items added to the order
     #[serde(default, rename = "order_details")]
     pub items: Option<HashMap<String, CheckoutAmountsItem>>,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for CheckoutAmounts {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "CheckoutAmounts",
+        fields: &[
+            Field::required("total", drift::NUMERIC),
+            Field::required("subtotal", drift::NUMERIC),
+            Field::required("delivery_fees", drift::NUMERIC),
+            Field::required("remaining_balance", drift::NUMERIC),
+            Field::required("balance", drift::NUMERIC),
+            Field::required("consigne_amount", drift::NUMERIC),
+            Field::required("national_tax", drift::NUMERIC),
+            Field::required("provincial_tax", drift::NUMERIC),
+            Field::required("coupon_discount_amount", drift::NUMERIC),
+            Field::required("order_donation", drift::NUMERIC),
+            Field::required("donation_discount", drift::NUMERIC),
+            Field::required("available_weekly", drift::NUMERIC),
+            Field::required("remaining_weekly", drift::NUMERIC),
+            Field::optional("order_details", drift::OBJECT_OR_NULL),
+        ],
+    };
 }
 
 #[cfg(test)]
@@ -265,5 +328,7 @@ mod tests {
 
         let amounts = serde_json::from_str::<CheckoutAmounts>(s);
         assert!(amounts.is_ok());
+
+        crate::se::assert_roundtrip(amounts.as_ref().unwrap());
     }
 }
@@ -1,21 +1,21 @@
 use std::collections::HashMap;
 
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se, ProductId};
 
 // An OrderItem represents an item from the marketplace
 // that has been added to the user's current order.
 // It contains product information as well as order-specific
 // information such as quantity and pricing details.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct OrderItem {
 
     // The product ID of the item
     #[serde(rename = "product_id")]
-    pub product_id: String,
+    pub product_id: ProductId,
 
     // The name of the item
     #[serde(rename = "p_name")]
@@ -55,28 +55,32 @@ pub struct OrderItem {
     // TODO -- not sure what this is
     #[serde(
         rename = "default_price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub default_price: Money<'static, iso::Currency>,
 
     // The product price that is shown on the marketplace
     #[serde(
         rename = "defined_price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub defined_price: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub price: Money<'static, iso::Currency>,
 
     // TODO -- not sure what this is
     #[serde(
         rename = "paid_price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub paid_price: Money<'static, iso::Currency>,
 
@@ -84,7 +88,8 @@ pub struct OrderItem {
     #[serde(
         default,
         rename = "avg_p_p",
-        deserialize_with = "de::money_optional"
+        deserialize_with = "de::money_optional",
+        serialize_with = "se::money_optional"
     )]
     pub ppu_price: Option<Money<'static, iso::Currency>>,
 
@@ -111,6 +116,18 @@ pub struct OrderItem {
     // What the quantity of item is measured in, e.g. "bag", "400g"
     #[serde(rename = "units")]
     pub units: String,
+
+    // The listed weight of one unit of the item
+    #[serde(
+        default,
+        rename = "weight",
+        deserialize_with = "deserialize_option_number_from_string"
+    )]
+    pub weight: Option<f64>,
+
+    // What the listed weight is measured in, e.g. "g"
+    #[serde(default, rename = "unit")]
+    pub weight_unit: Option<String>,
 }
 
 #[cfg(test)]
@@ -156,5 +173,7 @@ mod tests {
 
         let item: Result<OrderItem, _> = serde_json::from_str(s);
         assert!(item.is_ok());
+
+        crate::se::assert_roundtrip(item.as_ref().unwrap());
     }
 }
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::OrderStatus;
+
+// The stage of an order in its lifecycle, as identified by
+// its status code.
+//
+// Only the codes that have been observed are mapped: "2" for
+// an active order that can still be edited, and "4" for an
+// order being prepared. The codes of the other stages are
+// deliberately not guessed, since a wrong guess would report
+// an order as editable when it is not. Those stages can still
+// be told from an order's tracking, see `from_tracking`, and
+// unmapped codes are kept as `Unknown(code)`.
+//
+// As such, `Locked`, `Shipped`, `Delivered` and `Cancelled`
+// are never received from Lufa: they are only produced by
+// `from_tracking`, or read back from this crate's own
+// serialized orders.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrderLifecycle {
+    // The order is open, and can still be edited
+    Open,
+
+    // The order can no longer be edited, but is not being
+    // prepared yet. Never received from Lufa, see above.
+    Locked,
+
+    // The order is being packed
+    Preparing,
+
+    // The order is on its way. Never received from Lufa,
+    // see `from_tracking`.
+    Shipped,
+
+    // The order has been delivered. Never received from
+    // Lufa, see `from_tracking`.
+    Delivered,
+
+    // The order was cancelled. Never received from Lufa,
+    // see above.
+    Cancelled,
+
+    // A status code not known to the client
+    Unknown(u32),
+
+    // A status not known to the client that is not a code,
+    // kept as sent
+    UnknownName(String),
+}
+
+impl OrderLifecycle {
+    // The numeric code of the status, as sent by Lufa,
+    // if known
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            OrderLifecycle::Open => Some(2),
+            OrderLifecycle::Preparing => Some(4),
+            OrderLifecycle::Unknown(code) => Some(*code),
+            _ => None,
+        }
+    }
+
+    // The name of the stage, e.g. "open"
+    pub fn name(&self) -> &str {
+        match self {
+            OrderLifecycle::Open => "open",
+            OrderLifecycle::Locked => "locked",
+            OrderLifecycle::Preparing => "preparing",
+            OrderLifecycle::Shipped => "shipped",
+            OrderLifecycle::Delivered => "delivered",
+            OrderLifecycle::Cancelled => "cancelled",
+            OrderLifecycle::Unknown(_) => "unknown",
+            OrderLifecycle::UnknownName(name) => name,
+        }
+    }
+
+    // Whether the order can still be edited, if known
+    pub fn is_editable(&self) -> Option<bool> {
+        match self {
+            OrderLifecycle::Open => Some(true),
+            OrderLifecycle::Unknown(_) | OrderLifecycle::UnknownName(_) => None,
+            _ => Some(false),
+        }
+    }
+
+    // The stage of an order as reported by its tracking
+    pub fn from_tracking(status: &OrderStatus) -> Option<Self> {
+        match status {
+            OrderStatus::Preparing => Some(OrderLifecycle::Preparing),
+            OrderStatus::Shipped => Some(OrderLifecycle::Shipped),
+            OrderStatus::Delivered => Some(OrderLifecycle::Delivered),
+            OrderStatus::Unknown => None,
+        }
+    }
+}
+
+impl From<u32> for OrderLifecycle {
+    fn from(code: u32) -> Self {
+        match code {
+            2 => OrderLifecycle::Open,
+            4 => OrderLifecycle::Preparing,
+            code => OrderLifecycle::Unknown(code),
+        }
+    }
+}
+
+// Lufa sends the code as a string, e.g. `"4"`, but numbers
+// are accepted as well, as are the names of the stages
+// whose code is not known. Any other string is kept as
+// `UnknownName`, rather than failing the whole order.
+impl<'de> Deserialize<'de> for OrderLifecycle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Code {
+            Number(u32),
+            String(String),
+        }
+
+        let s = match Code::deserialize(deserializer)? {
+            Code::Number(n) => return Ok(OrderLifecycle::from(n)),
+            Code::String(s) => s,
+        };
+
+        if let Ok(code) = s.trim().parse::<u32>() {
+            return Ok(OrderLifecycle::from(code));
+        }
+
+        match s.trim() {
+            "locked" => Ok(OrderLifecycle::Locked),
+            "shipped" => Ok(OrderLifecycle::Shipped),
+            "delivered" => Ok(OrderLifecycle::Delivered),
+            "cancelled" => Ok(OrderLifecycle::Cancelled),
+            _ => Ok(OrderLifecycle::UnknownName(s)),
+        }
+    }
+}
+
+// Serialized as its code when known, or as its name
+impl Serialize for OrderLifecycle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.code() {
+            Some(code) => serializer.collect_str(&code),
+            None => serializer.serialize_str(self.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderLifecycle, OrderStatus};
+
+    #[test]
+    fn test_order_lifecycle() {
+        let status: OrderLifecycle = serde_json::from_str(r#""4""#).unwrap();
+        assert_eq!(status, OrderLifecycle::Preparing);
+        assert_eq!(status.is_editable(), Some(false));
+
+        let status: OrderLifecycle = serde_json::from_str("2").unwrap();
+        assert_eq!(status, OrderLifecycle::Open);
+        assert_eq!(status.is_editable(), Some(true));
+        assert_eq!(serde_json::to_string(&status).unwrap(), r#""2""#);
+
+        let status: OrderLifecycle = serde_json::from_str(r#""7""#).unwrap();
+        assert_eq!(status, OrderLifecycle::Unknown(7));
+        assert_eq!(status.is_editable(), None);
+
+        for status in [OrderLifecycle::Locked, OrderLifecycle::Cancelled] {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status.name()));
+            assert_eq!(serde_json::from_str::<OrderLifecycle>(&json).unwrap(), status);
+            assert_eq!(status.is_editable(), Some(false));
+        }
+
+        assert_eq!(
+            OrderLifecycle::from_tracking(&OrderStatus::Shipped),
+            Some(OrderLifecycle::Shipped)
+        );
+
+        let status: OrderLifecycle = serde_json::from_str(r#""pending""#).unwrap();
+        assert_eq!(status, OrderLifecycle::UnknownName("pending".to_string()));
+        assert_eq!(status.is_editable(), None);
+        assert_eq!(serde_json::to_string(&status).unwrap(), r#""pending""#);
+    }
+}
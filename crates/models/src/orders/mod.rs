@@ -7,6 +7,9 @@ pub use amounts::*;
 mod recipe;
 pub use recipe::*;
 
+mod lifecycle;
+pub use lifecycle::*;
+
 mod order;
 pub use order::*;
 
@@ -1,9 +1,13 @@
-use serde::Deserialize;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::de;
+use crate::{de, se, OrderId};
 
-use super::{CheckoutAmounts, OrderItem, Recipe};
+use super::{CheckoutAmounts, OrderItem, OrderLifecycle, Recipe};
+
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
 
 
 // An Order represents the user's current
@@ -11,23 +15,27 @@ use super::{CheckoutAmounts, OrderItem, Recipe};
 // corresponding to the status of the order,
 // the contents of the order, and the pricing
 // of the order.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Order {
 
     // The ID of the order
     #[serde(rename = "orderId")]
-    pub id: String,
+    pub id: OrderId,
 
     // The status of the order
     //
-    //  "4" => preparing?
-
+    //  "2" => open
+    //  "4" => preparing
     #[serde(rename = "orderStatus")]
-    pub status: String, // todo!
+    pub status: OrderLifecycle,
 
     // The date at which the order will be delivered
-    #[serde(rename = "orderDate")]
-    pub date: String, // todo!
+    #[serde(
+        rename = "orderDate",
+        deserialize_with = "de::date",
+        serialize_with = "se::date"
+    )]
+    pub date: NaiveDate,
 
     // A list of items in the order
     #[serde(rename = "orderDetails")]
@@ -43,15 +51,34 @@ pub struct Order {
     // The price breakdown of the order
     #[serde(rename = "checkoutAmounts")]
     pub amounts: CheckoutAmounts,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for Order {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "Order",
+        fields: &[
+            Field::required("orderId", drift::STRING),
+            Field::required("orderStatus", drift::NUMERIC),
+            Field::required("orderDate", drift::STRING),
+            Field::required("orderDetails", drift::ARRAY),
+            Field::required("orderRecipes", drift::ARRAY_OR_OBJECT),
+            Field::required("checkoutAmounts", drift::OBJECT).nested(CheckoutAmounts::SCHEMA),
+        ],
+    };
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Order;
+    use chrono::NaiveDate;
 
-    #[test]
-    fn test_deserialize() {
-        let s = r#"
+    use super::{Order, OrderLifecycle};
+
+    const ORDER: &str = r#"
       {
         "success": true,
         "orderDetails": [
@@ -848,7 +875,30 @@ mod tests {
       }
       "#;
 
-        let order: Result<Order, _> = serde_json::from_str(s);
+    #[test]
+    fn test_deserialize() {
+        let order: Result<Order, _> = serde_json::from_str(ORDER);
         assert!(order.is_ok());
+
+        let parsed = order.as_ref().unwrap();
+        assert_eq!(parsed.status, OrderLifecycle::Open);
+        assert_eq!(parsed.date, NaiveDate::from_ymd_opt(2024, 5, 12).unwrap());
+
+        crate::se::assert_roundtrip(order.as_ref().unwrap());
+
+        #[cfg(feature = "drift")]
+        assert_eq!(order.unwrap().extra["success"], true);
+    }
+
+    #[test]
+    fn test_unknown_status() {
+        // A status that is neither a code nor a known name
+        // does not fail the whole order
+        let s = ORDER.replace(r#""orderStatus": "2""#, r#""orderStatus": "pending""#);
+        let order: Order = serde_json::from_str(&s).unwrap();
+        assert_eq!(
+            order.status,
+            OrderLifecycle::UnknownName("pending".to_string())
+        );
     }
 }
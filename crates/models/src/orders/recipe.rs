@@ -1,17 +1,17 @@
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se, RecipeId};
 
 // A Recipe represents a recipe for a meal kit
 // from the marketplace.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Recipe {
 
     // The recipe ID
     #[serde(rename = "recipe_id")]
-    pub id: String,
+    pub id: RecipeId,
 
     // The number of portions the recipe produces
     #[serde(
@@ -21,27 +21,34 @@ pub struct Recipe {
     pub portions: usize,
 
     // The base total price of the recipe
-    #[serde(rename = "price", deserialize_with = "de::money")]
+    #[serde(
+        rename = "price",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub price: Money<'static, iso::Currency>,
 
     // The base price per portion of the recipe
     #[serde(
         rename = "price_per_portion",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub price_per_portion: Money<'static, iso::Currency>,
 
     // The active price per portion of the recipe
     #[serde(
         rename = "current_price_per_portion",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub current_price_per_portion: Money<'static, iso::Currency>,
 
     // The active total price of the recipe
     #[serde(
         rename = "current_price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub current_price: Money<'static, iso::Currency>,
 
@@ -51,12 +58,12 @@ pub struct Recipe {
 }
 
 // An Ingredient ...todo
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Ingredient {
     #[serde(rename = "options")]
     pub options: Vec<IngredientOption>,
 }
 
 // An IngredientOption ...todo
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct IngredientOption {}
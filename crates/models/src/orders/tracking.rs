@@ -1,10 +1,15 @@
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use chrono_tz::{America, Tz};
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se, OrderId};
 
-#[derive(Deserialize, Debug)]
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub enum OrderStatus {
     #[serde(rename = "preparing")]
     Preparing,
@@ -14,16 +19,58 @@ pub enum OrderStatus {
 
     #[serde(rename = "delivered")]
     Delivered,
+
+    // A status not known to the client
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+impl OrderStatus {
+    // The step of the tracking progress that the status
+    // corresponds to
+    pub fn step(&self) -> Option<usize> {
+        match self {
+            OrderStatus::Preparing => Some(0),
+            OrderStatus::Shipped => Some(1),
+            OrderStatus::Delivered => Some(2),
+            OrderStatus::Unknown => None,
+        }
+    }
+}
+
+// The type of delivery scheduled for an order
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryType {
+    // Delivered to the user's door
+    #[serde(rename = "HD")]
+    HomeDelivery,
+
+    // Picked up by the user at a pickup point
+    #[serde(rename = "PUP")]
+    PickupPoint,
+
+    // A type of delivery not known to the client
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+// The window within which an order is estimated
+// to be delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EtaWindow {
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
 }
 
 // Represents the current tracking
 // information for an order
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(from = "RawOrderTracking")]
 pub struct OrderTracking {
     // The order ID that this tracking
     // corresponds to
     #[serde(rename = "order_id")]
-    pub order_id: String,
+    pub order_id: OrderId,
 
     // The current status of the order
     //
@@ -37,7 +84,7 @@ pub struct OrderTracking {
     pub description: String,
 
     // The current step/progress of the
-    // order
+    // order, see `OrderStatus::step`
     //
     // 0 => preparing
     // 1 => shipped
@@ -46,20 +93,23 @@ pub struct OrderTracking {
     pub step: usize,
 
     // The date at which the order
-    // is scheduled for delivery
+    // is scheduled for delivery, if
+    // it could be understood
+    #[serde(skip)]
+    pub delivery_date: Option<NaiveDate>,
+
+    // The delivery date as sent by Lufa,
+    // e.g. "Sunday, May 12th, 2024"
     #[serde(rename = "delivery_date")]
-    pub delivery_date: String,
+    pub delivery_date_raw: String,
 
     // The number of boxes that the order
     // is packed in
-    #[serde(
-        rename = "number_box_needed",
-        deserialize_with = "deserialize_number_from_string"
-    )]
+    #[serde(rename = "number_box_needed")]
     pub boxes: usize,
 
     // The total cost of the order
-    #[serde(rename = "order_amount", deserialize_with = "de::money")]
+    #[serde(rename = "order_amount", serialize_with = "se::money")]
     pub order_amount: Money<'static, iso::Currency>,
 
     // The number of stops prior
@@ -67,10 +117,22 @@ pub struct OrderTracking {
     #[serde(rename = "stops_before")]
     pub stops_before: usize,
 
-    // The estimated time at which
-    // the order will be delivered
+    // The window within which the order
+    // is estimated to be delivered, in
+    // Montreal time on its delivery date.
+    // A single time is a window that
+    // starts and ends at that time.
+    //
+    // None until the order is on its way,
+    // or when the ETA or the delivery
+    // date cannot be understood.
+    #[serde(skip)]
+    pub eta: Option<EtaWindow>,
+
+    // The estimated time of arrival as
+    // sent by Lufa, e.g. "15:15"
     #[serde(rename = "eta")]
-    pub eta: String, // TODO
+    pub eta_raw: String,
 
     // The name of the driver delivering
     // the order
@@ -96,7 +158,7 @@ pub struct OrderTracking {
     // "HD"   => Home Delivery
     // "PUP"  => Pickup
     #[serde(rename = "delivery_type")]
-    pub delivery_type: String,
+    pub delivery_type: DeliveryType,
 
     // The address at which the order
     // will be delivered to
@@ -107,39 +169,225 @@ pub struct OrderTracking {
     // empty boxes outside
     #[serde(rename = "reminder")]
     pub reminder: String,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
+}
+
+// The tracking as sent by Lufa, from which the delivery
+// date and the ETA are then parsed. See `OrderTracking`.
+#[derive(Deserialize)]
+struct RawOrderTracking {
+    order_id: OrderId,
+    status: OrderStatus,
+    desc: String,
+    step: usize,
+    delivery_date: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    number_box_needed: usize,
+    #[serde(deserialize_with = "de::money")]
+    order_amount: Money<'static, iso::Currency>,
+    stops_before: usize,
+    eta: String,
+    driver_name: String,
+    company_name: String,
+    formatted_company_phone_number: String,
+    formatted_pup_phone_number: String,
+    delivery_type: DeliveryType,
+    address: String,
+    reminder: String,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    extra: crate::Extra,
+}
+
+impl From<RawOrderTracking> for OrderTracking {
+    fn from(raw: RawOrderTracking) -> Self {
+        let delivery_date = de::parse_date(&raw.delivery_date);
+        let eta = delivery_date.and_then(|date| parse_eta(&raw.eta, date));
+
+        OrderTracking {
+            order_id: raw.order_id,
+            status: raw.status,
+            description: raw.desc,
+            step: raw.step,
+            delivery_date,
+            delivery_date_raw: raw.delivery_date,
+            boxes: raw.number_box_needed,
+            order_amount: raw.order_amount,
+            stops_before: raw.stops_before,
+            eta,
+            eta_raw: raw.eta,
+            driver_name: raw.driver_name,
+            company_name: raw.company_name,
+            company_phone: raw.formatted_company_phone_number,
+            pickup_phone: raw.formatted_pup_phone_number,
+            delivery_type: raw.delivery_type,
+            address: raw.address,
+            reminder: raw.reminder,
+            extra: raw.extra,
+        }
+    }
+}
+
+// Parses an estimated time of arrival into a window on
+// the given date. A window past midnight ends on the
+// next day.
+fn parse_eta(eta: &str, date: NaiveDate) -> Option<EtaWindow> {
+    let eta = eta.trim();
+    if eta.is_empty() {
+        return None;
+    }
+
+    let (start, end) = eta.split_once(['-', '–']).unwrap_or((eta, eta));
+    let (start, end) = (parse_eta_time(start)?, parse_eta_time(end)?);
+
+    let at = |date: NaiveDate, time| {
+        date.and_time(time)
+            .and_local_timezone(America::Montreal)
+            .earliest()
+    };
+
+    let end_date = if end < start { date.succ_opt()? } else { date };
+
+    Some(EtaWindow {
+        start: at(date, start)?,
+        end: at(end_date, end)?,
+    })
+}
+
+// Parses a time of the estimated time of arrival,
+// e.g. "15:15", "15h15", "3:15 PM"
+fn parse_eta_time(s: &str) -> Option<NaiveTime> {
+    ["%H:%M", "%Hh%M", "%I:%M %p"]
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(s.trim(), format).ok())
+}
+
+#[cfg(feature = "drift")]
+impl KnownSchema for OrderTracking {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "OrderTracking",
+        fields: &[
+            Field::required("order_id", drift::STRING),
+            Field::required("status", drift::STRING),
+            Field::required("desc", drift::STRING),
+            Field::required("step", drift::NUMBER),
+            Field::required("delivery_date", drift::STRING),
+            Field::required("number_box_needed", drift::NUMERIC),
+            Field::required("order_amount", drift::NUMERIC),
+            Field::required("stops_before", drift::NUMBER),
+            Field::required("eta", drift::STRING),
+            Field::required("driver_name", drift::STRING),
+            Field::required("company_name", drift::STRING),
+            Field::required("formatted_company_phone_number", drift::STRING),
+            Field::required("formatted_pup_phone_number", drift::STRING),
+            Field::required("delivery_type", drift::STRING),
+            Field::required("address", drift::STRING),
+            Field::required("reminder", drift::STRING),
+        ],
+    };
 }
 
 #[cfg(test)]
 mod tests {
-    use super::OrderTracking;
+    use chrono::{NaiveDate, TimeZone};
+    use chrono_tz::America;
 
-    #[test]
-    fn test_deserialize() {
-        let s = r#"
-        {
+    use super::{DeliveryType, OrderStatus, OrderTracking};
+
+    fn tracking(delivery_date: &str, eta: &str, delivery_type: &str) -> OrderTracking {
+        let s = format!(
+            r#"
+        {{
           "status": "preparing",
           "step": 0,
-          "delivery_date": "Sunday, May 12th, 2024",
+          "delivery_date": "{}",
           "order_id": "12345678",
           "number_box_needed": "1",
           "order_amount_label": "Total: ",
           "order_amount": "$130.31",
           "stops_before": 13,
-          "eta": "15:15",
+          "eta": "{}",
           "driver_name": "John",
           "company_name": "Yalla Go",
           "company_phone_number": "514-123-4567",
           "formatted_company_phone_number": "+15141234567",
           "pup_phone_number": "5141234567",
           "formatted_pup_phone_number": "+15141234567",
-          "delivery_type": "HD",
+          "delivery_type": "{}",
           "address": "7070 Henri Julien Ave, Montréal",
           "reminder": "Don’t forget to leave any empty baskets from your previous order at your front door for our delivery driver to pick up.",
           "desc": "We’re preparing your order. Once it’s on its way, your delivery ETA will appear."
-        }
-        "#;
+        }}
+        "#,
+            delivery_date, eta, delivery_type
+        );
+
+        serde_json::from_str(&s).unwrap()
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let parsed = tracking("Sunday, May 12th, 2024", "15:15", "HD");
+
+        assert_eq!(parsed.status.step(), Some(parsed.step));
+        assert_eq!(parsed.delivery_type, DeliveryType::HomeDelivery);
+        assert_eq!(
+            parsed.delivery_date,
+            Some(NaiveDate::from_ymd_opt(2024, 5, 12).unwrap())
+        );
+
+        let window = parsed.eta.unwrap();
+        let expected = America::Montreal
+            .with_ymd_and_hms(2024, 5, 12, 15, 15, 0)
+            .unwrap();
+        assert_eq!(window.start, expected);
+        assert_eq!(window.end, expected);
+
+        crate::se::assert_roundtrip(&parsed);
+    }
+
+    #[test]
+    fn test_eta_and_status() {
+        let date = "2024-05-12";
+        let at = |d, h, m| {
+            America::Montreal
+                .with_ymd_and_hms(2024, 5, d, h, m, 0)
+                .unwrap()
+        };
+
+        let window = tracking(date, "15h15 - 15h45", "HD").eta.unwrap();
+        assert_eq!((window.start, window.end), (at(12, 15, 15), at(12, 15, 45)));
+
+        let window = tracking(date, "11:30 PM – 0:30", "HD").eta.unwrap();
+        assert_eq!((window.start, window.end), (at(12, 23, 30), at(13, 0, 30)));
+
+        // An ETA that cannot be understood does not fail
+        // the tracking, and is kept as sent
+        let unclear = tracking(date, "soon", "LOCKER");
+        assert_eq!(unclear.eta_raw, "soon");
+        assert_eq!(unclear.eta, None);
+        assert_eq!(unclear.delivery_type, DeliveryType::Unknown);
+        assert_eq!(tracking(date, "", "PUP").eta, None);
+
+        let status: OrderStatus = serde_json::from_str(r#""lost""#).unwrap();
+        assert_eq!(status, OrderStatus::Unknown);
+        assert_eq!(status.step(), None);
+    }
+
+    #[test]
+    fn test_unclear_delivery_date() {
+        // Nor does a delivery date that cannot be understood,
+        // though the ETA cannot be placed without it
+        let unclear = tracking("Tomorrow", "15:15", "HD");
+        assert_eq!(unclear.delivery_date, None);
+        assert_eq!(unclear.delivery_date_raw, "Tomorrow");
+        assert_eq!(unclear.eta, None);
+        assert_eq!(unclear.eta_raw, "15:15");
 
-        let tracking: Result<OrderTracking, _> = serde_json::from_str(s);
-        assert!(tracking.is_ok());
+        crate::se::assert_roundtrip(&unclear);
     }
 }
@@ -1,15 +1,15 @@
 use chrono::DateTime;
 use chrono_tz::Tz;
 use rusty_money::{iso, Money};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se};
 
 // Data corresponding to the Lufa incentive program.
 // Contains various statistics pertaining to the 
 // user's order habits
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct IncentiveData {
 
     // The current ISO week of the year
@@ -48,35 +48,40 @@ pub struct IncentiveData {
     // The total amount spent this year
     #[serde(
         rename = "amount_spent",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub amount_spent: Money<'static, iso::Currency>,
 
     // The current amount earned for the giveback program
     #[serde(
         rename = "current_earnings",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub current_earnings: Money<'static, iso::Currency>,
 
     // The projected amount earnined for the giveback program
     #[serde(
         rename = "projected_earnings",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub projected_earnings: Money<'static, iso::Currency>,
 
     // The datetime at which the statistics were generated
     #[serde(
         rename = "created_at",
-        deserialize_with = "de::timestamp"
+        deserialize_with = "de::timestamp",
+        serialize_with = "se::timestamp"
     )]
     pub created_at: DateTime<Tz>,
 
     // The datetime at which the statistics were updated
     #[serde(
         rename = "updated_at",
-        deserialize_with = "de::timestamp"
+        deserialize_with = "de::timestamp",
+        serialize_with = "se::timestamp"
     )]
     pub updated_at: DateTime<Tz>,
 
@@ -94,6 +99,7 @@ pub struct IncentiveData {
     pub percentage_of_time: String,
 }
 
+#[cfg(test)]
 mod tests {
     use super::IncentiveData;
 
@@ -122,5 +128,7 @@ mod tests {
 
         let data: Result<IncentiveData, _> = serde_json::from_str(s);
         assert!(data.is_ok());
+
+        crate::se::assert_roundtrip(data.as_ref().unwrap());
     }
 }
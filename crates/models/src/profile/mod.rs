@@ -1,5 +1,6 @@
 mod incentive;
 pub use incentive::*;
 
+#[allow(clippy::module_inception)]
 mod profile;
 pub use profile::*;
@@ -1,19 +1,22 @@
 use chrono::NaiveDate;
 use rusty_money::iso;
 use rusty_money::Money;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_aux::prelude::*;
 
-use crate::de;
+use crate::{de, se, UserId};
 
 use super::IncentiveData;
 
+#[cfg(feature = "drift")]
+use crate::drift::{self, Field, KnownSchema};
+
 // A Profile represents data that is user-specific
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Profile {
     // The user ID
     #[serde(rename = "user_id")]
-    pub user_id: String,
+    pub user_id: UserId,
 
     // The user's first name
     //
@@ -38,19 +41,28 @@ pub struct Profile {
     pub subscription_type: String,
 
     // The date at which the user was created
-    #[serde(rename = "user_created", deserialize_with = "de::date")]
+    #[serde(
+        rename = "user_created",
+        deserialize_with = "de::date",
+        serialize_with = "se::date"
+    )]
     pub user_created: NaiveDate,
 
     // The credits balance for the user. A positive balance
     // indicates you are owed money, while a negative balance
     // indicates that you owe money
-    #[serde(rename = "user_credits", deserialize_with = "de::money")]
+    #[serde(
+        rename = "user_credits",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub user_credits: Money<'static, iso::Currency>,
 
     // The date at which the user became a superlufavore
     #[serde(
         rename = "became_superlufavore_on",
-        deserialize_with = "de::date"
+        deserialize_with = "de::date",
+        serialize_with = "se::date"
     )]
     pub became_superlufavore_on: NaiveDate,
 
@@ -62,7 +74,11 @@ pub struct Profile {
     pub family_size: usize,
 
     // The date at which the user was created
-    #[serde(rename = "created", deserialize_with = "de::date")]
+    #[serde(
+        rename = "created",
+        deserialize_with = "de::date",
+        serialize_with = "se::date"
+    )]
     pub created: NaiveDate,
 
     // TODO -- not sure
@@ -91,7 +107,8 @@ pub struct Profile {
     // under the user
     #[serde(
         rename = "min_basket_price",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub min_basket_price: Money<'static, iso::Currency>,
 
@@ -102,14 +119,16 @@ pub struct Profile {
     // TODO -- not sure
     #[serde(
         rename = "user_free_credits_spendable",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub user_free_credits_spendable: Money<'static, iso::Currency>,
 
     // TODO -- not sure
     #[serde(
         rename = "user_all_credits_spendable",
-        deserialize_with = "de::money"
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
     )]
     pub user_all_credits_spendable: Money<'static, iso::Currency>,
 
@@ -118,7 +137,11 @@ pub struct Profile {
     pub incentive_data: IncentiveData,
 
     // The projected earnings amount for the giveback program
-    #[serde(rename = "earnings", deserialize_with = "de::money")]
+    #[serde(
+        rename = "earnings",
+        deserialize_with = "de::money",
+        serialize_with = "se::money"
+    )]
     pub earnings: Money<'static, iso::Currency>,
 
     // TODO -- not sure
@@ -128,8 +151,44 @@ pub struct Profile {
     // TODO -- not sure
     #[serde(default, rename = "could_give_remaining_balance")]
     pub could_give_remaining_balance: bool,
+
+    #[cfg_attr(feature = "drift", serde(flatten))]
+    #[cfg_attr(not(feature = "drift"), serde(skip))]
+    pub extra: crate::Extra,
 }
 
+#[cfg(feature = "drift")]
+impl KnownSchema for Profile {
+    const SCHEMA: &'static drift::Schema = &drift::Schema {
+        model: "Profile",
+        fields: &[
+            Field::required("user_id", drift::STRING),
+            Field::required("first_name", drift::STRING),
+            Field::required("user_name", drift::STRING),
+            Field::required("donation_name", drift::STRING),
+            Field::required("subscription_type", drift::STRING),
+            Field::required("user_created", drift::STRING),
+            Field::required("user_credits", drift::NUMERIC),
+            Field::required("became_superlufavore_on", drift::STRING),
+            Field::required("family_size", drift::NUMERIC),
+            Field::required("created", drift::STRING),
+            Field::optional("giveback_donation_percent", drift::NUMERIC_OR_NULL),
+            Field::required("anonymous", drift::BOOL),
+            Field::required("subscriptions_order_prepopulation_method", drift::STRING),
+            Field::required("orders_order_prepopulation_method", drift::STRING),
+            Field::required("min_basket_price", drift::NUMERIC),
+            Field::required("reactivation", drift::BOOL),
+            Field::required("user_free_credits_spendable", drift::NUMERIC),
+            Field::required("user_all_credits_spendable", drift::NUMERIC),
+            Field::required("incentive_data", drift::OBJECT),
+            Field::required("earnings", drift::NUMERIC),
+            Field::optional("dg_company_coordinator", drift::BOOL),
+            Field::optional("could_give_remaining_balance", drift::BOOL),
+        ],
+    };
+}
+
+#[cfg(test)]
 mod tests {
     use super::Profile;
 
@@ -186,6 +245,8 @@ mod tests {
         let profile: Result<Profile, _> = serde_json::from_str(s);
         assert!(profile.is_ok());
 
+        crate::se::assert_roundtrip(profile.as_ref().unwrap());
+
         let profile = profile.unwrap();
         assert_eq!(profile.user_id, "123456");
     }
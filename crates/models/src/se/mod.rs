@@ -0,0 +1,17 @@
+mod timestamp;
+pub use timestamp::*;
+
+mod money;
+pub use money::*;
+
+// Asserts that a model serializes into a canonical form
+// that parses back into the same model
+#[cfg(test)]
+pub(crate) fn assert_roundtrip<T>(model: &T)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let json = serde_json::to_string(model).unwrap();
+    let roundtrip: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(&roundtrip, model);
+}
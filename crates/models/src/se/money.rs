@@ -0,0 +1,87 @@
+// Serializers for money/currency amounts, the counterparts
+// of the deserializers in `de::money`.
+
+use rusty_money::{iso, Money};
+use serde::Serializer;
+
+// Serializes a Money instance into its canonical form: a plain
+// decimal string, e.g. "1000.00", which `de::money` parses back
+// as is. Amounts have two decimals, or at least four when more
+// precise, since a string such as "1.234" would be read with a
+// thousands separator.
+pub fn money<S>(money: &Money<'static, iso::Currency>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let amount = money.amount().normalize();
+    let decimals = match amount.scale() {
+        0..=2 => 2,
+        scale => scale.max(4) as usize,
+    };
+
+    serializer.collect_str(&format_args!("{:.*}", decimals, amount))
+}
+
+// Optionally serializes a Money instance, as `null` if absent
+pub fn money_optional<S>(
+    money: &Option<Money<'static, iso::Currency>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match money {
+        Some(m) => self::money(m, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use rusty_money::{iso, Money};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{de, se};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Price {
+        #[serde(deserialize_with = "de::money", serialize_with = "se::money")]
+        price: Money<'static, iso::Currency>,
+
+        #[serde(
+            default,
+            deserialize_with = "de::money_optional",
+            serialize_with = "se::money_optional"
+        )]
+        discount: Option<Money<'static, iso::Currency>>,
+    }
+
+    #[test]
+    fn test_serialize_money() {
+        let cases = [
+            (r#"{ "price": "$ 1,000.00" }"#, r#"{"price":"1000.00","discount":null}"#),
+            (r#"{ "price": 5, "discount": "" }"#, r#"{"price":"5.00","discount":null}"#),
+            (r#"{ "price": "-1,5", "discount": 3.5 }"#, r#"{"price":"-1.50","discount":"3.50"}"#),
+            (r#"{ "price": 140.66000366210938 }"#, r#"{"price":"140.6600036621094","discount":null}"#),
+            (r#"{ "price": "140.66000366210938" }"#, r#"{"price":"140.66000366210938","discount":null}"#),
+        ];
+
+        for (input, canonical) in cases {
+            let price: Price = serde_json::from_str(input).unwrap();
+            let json = serde_json::to_string(&price).unwrap();
+            assert_eq!(json, canonical);
+
+            let parsed: Price = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, price);
+        }
+
+        let price = Price {
+            price: Money::from_decimal(Decimal::new(1234, 3), iso::CAD),
+            discount: None,
+        };
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!(json, r#"{"price":"1.2340","discount":null}"#);
+        assert_eq!(serde_json::from_str::<Price>(&json).unwrap(), price);
+    }
+}
@@ -0,0 +1,70 @@
+// Serializers for dates and timestamps, the counterparts
+// of the deserializers in `de::timestamp`.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use chrono_tz::{America, Tz};
+use serde::Serializer;
+
+// Serializes a NaiveDate in the form of "2024-05-07"
+pub fn date<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&date.format("%Y-%m-%d"))
+}
+
+// Serializes a DateTime in the form of "2024-05-07 00:32:54",
+// in Montreal time like the API sends them. The zero date the
+// API uses for missing timestamps is kept as is.
+pub fn timestamp<S>(timestamp: &DateTime<Tz>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let naive = timestamp.with_timezone(&America::Montreal).naive_local();
+
+    match naive {
+        NaiveDateTime::MIN => serializer.serialize_str("0000-00-00 00:00:00"),
+        naive => serializer.collect_str(&naive.format("%Y-%m-%d %H:%M:%S")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate};
+    use chrono_tz::Tz;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{de, se};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Dates {
+        #[serde(deserialize_with = "de::date", serialize_with = "se::date")]
+        date: NaiveDate,
+
+        #[serde(deserialize_with = "de::timestamp", serialize_with = "se::timestamp")]
+        timestamp: DateTime<Tz>,
+    }
+
+    #[test]
+    fn test_serialize_dates() {
+        let cases = [
+            (
+                r#"{ "date": "January 01, 2021", "timestamp": "2024-05-07 00:32:54" }"#,
+                r#"{"date":"2021-01-01","timestamp":"2024-05-07 00:32:54"}"#,
+            ),
+            (
+                r#"{ "date": "2022-01-01", "timestamp": "0000-00-00 00:00:00" }"#,
+                r#"{"date":"2022-01-01","timestamp":"0000-00-00 00:00:00"}"#,
+            ),
+        ];
+
+        for (input, canonical) in cases {
+            let dates: Dates = serde_json::from_str(input).unwrap();
+            let json = serde_json::to_string(&dates).unwrap();
+            assert_eq!(json, canonical);
+
+            let parsed: Dates = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, dates);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use chrono::NaiveDate;
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
 
 // Parses a NaiveDate from a string, assuming the string
 // is in the format of mm/YY
@@ -15,3 +15,12 @@ where
         NaiveDate::parse_from_str(with_date.as_str(), "%m/%y/%d").map_err(D::Error::custom)?;
     Ok(date)
 }
+
+// Serializes a NaiveDate into a string in the format
+// of mm/YY, the counterpart of `naive_date_from_str`
+pub fn naive_date_to_str<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&date.format("%m/%y"))
+}